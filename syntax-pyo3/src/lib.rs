@@ -1,6 +1,9 @@
 #[pyo3::pymodule]
 mod sel_simplifier {
-    use pyo3::{exceptions::PyRuntimeError, prelude::*};
+    use pyo3::{
+        exceptions::{PyDeprecationWarning, PyRuntimeError},
+        prelude::*,
+    };
     use std::collections::HashMap;
     use syntax::Simplifier;
 
@@ -55,8 +58,8 @@ mod sel_simplifier {
     }
 
     #[pyfunction]
-    pub fn simplify_expression(input: &str) -> PyResult<CanonEquation> {
-        let simplifier = Simplifier();
+    pub fn simplify_equation(input: &str) -> PyResult<CanonEquation> {
+        let simplifier = Simplifier::new();
         let simplified_equation = simplifier.simplify_equation(input);
 
         match simplified_equation {
@@ -64,4 +67,18 @@ mod sel_simplifier {
             Err(e) => Err(PyRuntimeError::new_err(format!("{e}"))),
         }
     }
+
+    /// Deprecated, use `simplify_equation` instead. Despite its name, the input
+    /// must be a full equation containing `=`.
+    #[pyfunction]
+    pub fn simplify_expression(py: Python<'_>, input: &str) -> PyResult<CanonEquation> {
+        PyErr::warn(
+            py,
+            &py.get_type::<PyDeprecationWarning>(),
+            c"simplify_expression expects a full equation, use simplify_equation instead",
+            1,
+        )?;
+
+        simplify_equation(input)
+    }
 }
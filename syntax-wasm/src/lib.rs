@@ -0,0 +1,32 @@
+use js_sys::{Object, Reflect};
+use syntax::Simplifier;
+use wasm_bindgen::prelude::*;
+
+/// Simplifies an equation and returns it as a JS object of the shape
+/// `{ terms: { [variable]: number }, constant: number }`.
+#[wasm_bindgen]
+pub fn simplify(input: &str) -> Result<JsValue, JsValue> {
+    let simplifier = Simplifier::new();
+    let equation = simplifier
+        .simplify_equation(input)
+        .map_err(|e| JsValue::from_str(&format!("{e}")))?;
+
+    let terms = Object::new();
+    for (variable, coefficient) in &equation.terms {
+        Reflect::set(
+            &terms,
+            &JsValue::from_str(variable),
+            &JsValue::from_f64(*coefficient),
+        )?;
+    }
+
+    let result = Object::new();
+    Reflect::set(&result, &JsValue::from_str("terms"), &terms)?;
+    Reflect::set(
+        &result,
+        &JsValue::from_str("constant"),
+        &JsValue::from_f64(equation.constant),
+    )?;
+
+    Ok(result.into())
+}
@@ -0,0 +1,29 @@
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use std::io::Cursor;
+use syntax::{Evaluator, Lexer, Simplifier, Token, Tokenizer};
+
+const NUMERIC: &str = "2^10 - 3 * (4 + 5) / \\sqrt(9) = 1000 + 2 * 3 - 4";
+
+fn numeric_paths(c: &mut Criterion) {
+    let tokens: Vec<Token> = Tokenizer::new(Cursor::new(NUMERIC))
+        .map(|token| token.unwrap())
+        .collect();
+    let equation = Lexer::new(tokens).equation().unwrap();
+    let evaluator = Evaluator::new();
+
+    let mut group = c.benchmark_group("numeric");
+    group.bench_function("fast path", |b| {
+        b.iter(|| evaluator.evaluate_constant(black_box(&equation.left)))
+    });
+    group.bench_function("general path", |b| {
+        b.iter(|| evaluator.evaluate_expression(black_box(&equation.left)))
+    });
+    group.finish();
+
+    c.bench_function("simplify_equation numeric", |b| {
+        b.iter(|| Simplifier::new().simplify_equation(black_box(NUMERIC)))
+    });
+}
+
+criterion_group!(benches, numeric_paths);
+criterion_main!(benches);
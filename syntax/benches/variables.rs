@@ -0,0 +1,20 @@
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use syntax::Simplifier;
+
+/// An equation with a hundred terms, each its own variable from `x1` to `x100`.
+fn many_variables() -> String {
+    let terms: Vec<String> = (1..=100).map(|index| format!("{index}x{index}")).collect();
+
+    format!("{} = 1", terms.join(" + "))
+}
+
+fn simplify_many_variables(c: &mut Criterion) {
+    let input = many_variables();
+
+    c.bench_function("simplify_equation many variables", |b| {
+        b.iter(|| Simplifier::new().simplify_equation(black_box(&input)))
+    });
+}
+
+criterion_group!(benches, simplify_many_variables);
+criterion_main!(benches);
@@ -0,0 +1,94 @@
+//! Prints the canonical form of equations given as arguments, or read one per
+//! line from stdin.
+//!
+//! ```text
+//! sel [--format sexpr|infix|latex|json] [EQUATION...]
+//! ```
+
+use std::io::{self, BufRead};
+use std::process::ExitCode;
+
+use syntax::{CanonicalEquation, Simplifier};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Sexpr,
+    Infix,
+    Latex,
+    Json,
+}
+
+impl Format {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "sexpr" => Some(Self::Sexpr),
+            "infix" => Some(Self::Infix),
+            "latex" => Some(Self::Latex),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    fn render(self, equation: &CanonicalEquation) -> String {
+        match self {
+            Self::Sexpr => equation.to_sexpr(),
+            Self::Infix => equation.to_infix(),
+            Self::Latex => equation.to_latex(),
+            Self::Json => equation.to_json(),
+        }
+    }
+}
+
+const USAGE: &str = "usage: sel [--format sexpr|infix|latex|json] [EQUATION...]";
+
+fn main() -> ExitCode {
+    let mut format = Format::Infix;
+    let mut equations = Vec::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let name = match arg.strip_prefix("--format") {
+            Some("") => args.next(),
+            Some(rest) => rest.strip_prefix('=').map(String::from),
+            None => {
+                equations.push(arg);
+                continue;
+            }
+        };
+
+        match name.as_deref().and_then(Format::parse) {
+            Some(parsed) => format = parsed,
+            None => {
+                eprintln!("{USAGE}");
+                return ExitCode::from(2);
+            }
+        }
+    }
+
+    if equations.is_empty() {
+        for line in io::stdin().lock().lines() {
+            match line {
+                Ok(line) if line.trim().is_empty() => {}
+                Ok(line) => equations.push(line),
+                Err(error) => {
+                    eprintln!("{error}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+    }
+
+    let simplifier = Simplifier::new();
+    let mut status = ExitCode::SUCCESS;
+    for equation in &equations {
+        match simplifier.simplify_equation(equation) {
+            Ok(canonical) => println!("{}", format.render(&canonical)),
+            Err(error) => {
+                eprintln!("{equation}: {error}");
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    status
+}
@@ -0,0 +1,53 @@
+use alloc::string::String;
+
+/// Renders `line` followed by a caret `^` under the given 1-based column, the same
+/// convention used by [`Token`](crate::tokenizer::Token) columns and tokenizer
+/// errors.
+///
+/// Columns count bytes, so the padding skips UTF-8 continuation bytes and keeps
+/// tabs to stay aligned with the rendered line.
+pub fn render_error_location(line: &str, column: usize) -> String {
+    let line = line.trim_end_matches(['\n', '\r']);
+    let mut rendered = String::with_capacity(line.len() * 2 + 2);
+    rendered.push_str(line);
+    rendered.push('\n');
+
+    for &byte in line.as_bytes().iter().take(column.saturating_sub(1)) {
+        match byte {
+            b'\t' => rendered.push('\t'),
+            0x80..=0xBF => {}
+            _ => rendered.push(' '),
+        }
+    }
+    rendered.push('^');
+
+    rendered
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::diagnostic::render_error_location;
+    use crate::tokenizer::{Tokenizer, TokenizerError};
+    use std::io::Cursor;
+
+    #[test]
+    fn test_caret_under_unknown_character() {
+        let source = "2 @ 3";
+        let error = Tokenizer::new(Cursor::new(source))
+            .find_map(|token| token.err())
+            .unwrap();
+
+        let column = match error {
+            TokenizerError::UnknownCharacter(_, _, column) => column,
+            _ => panic!("Expected an unknown character error"),
+        };
+
+        assert_eq!(render_error_location(source, column), "2 @ 3\n  ^");
+    }
+
+    #[test]
+    fn test_caret_keeps_tabs() {
+        assert_eq!(render_error_location("\tx = 1\n", 2), "\tx = 1\n\t^");
+        assert_eq!(render_error_location("x", 1), "x\n^");
+    }
+}
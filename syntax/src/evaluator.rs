@@ -1,17 +1,29 @@
 mod error;
 mod value;
 
-pub use crate::evaluator::error::EvaluatorError;
-use crate::evaluator::error::{EvaluatorErrorType, EvaluatorResult};
+use crate::evaluator::error::EvaluatorResult;
+pub use crate::evaluator::error::{EquationSide, EvaluatorError, EvaluatorErrorType};
 pub use crate::evaluator::value::Value;
 use crate::expression::{Expression, ExpressionType};
+use crate::lexer::{Equation, Relation};
+use crate::rational::Rational;
 use crate::tokenizer::{Token, TokenType};
+use std::cell::Cell;
 use std::collections::HashMap;
 
 type Function = fn(f64) -> f64;
 
+/// How many nested expressions are evaluated before giving up. Chains of
+/// additions and subtractions are evaluated in a loop, so this bounds nesting
+/// like `((x))` or `2*(3*(x))` rather than the number of terms. Each level takes
+/// a few KiB of stack in debug builds, which is what keeps this small.
+const DEFAULT_MAX_DEPTH: usize = 128;
+
 pub struct Evaluator {
     functions: HashMap<String, Function>,
+    depth: Cell<usize>,
+    max_depth: usize,
+    max_terms: Option<usize>,
 }
 
 macro_rules! float_function {
@@ -20,6 +32,12 @@ macro_rules! float_function {
     };
 }
 
+impl Default for Evaluator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Evaluator {
     pub fn new() -> Self {
         let functions = HashMap::from([
@@ -35,10 +53,162 @@ impl Evaluator {
             float_function!("floor", floor),
             float_function!("ceil", ceil),
         ]);
-        Self { functions }
+        Self {
+            functions,
+            depth: Cell::new(0),
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_terms: None,
+        }
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Fails with [`EvaluatorErrorType::TermLimitExceeded`] when distributing a
+    /// product would produce more than `max_terms` terms, instead of allocating
+    /// them. Unlimited by default.
+    pub fn with_max_terms(mut self, max_terms: usize) -> Self {
+        self.max_terms = Some(max_terms);
+        self
     }
 
     pub fn evaluate_expression(&self, expression: &Expression) -> EvaluatorResult<Value> {
+        self.nested(expression, || self.evaluate_node(expression))
+    }
+
+    /// Evaluates `left - right`, moving every term of the equation to one side,
+    /// as a flat sum with like terms combined. A single remaining term is returned
+    /// as a monomial, and [`Value::zero`] if every term cancels.
+    pub fn evaluate_equation(&self, equation: &Equation) -> EvaluatorResult<Value> {
+        let left = self
+            .evaluate_expression(&equation.left)
+            .map_err(|e| e.on_side(EquationSide::Left))?;
+        let right = self
+            .evaluate_expression(&equation.right)
+            .map_err(|e| e.on_side(EquationSide::Right))?;
+
+        Ok(combine_like_terms(vec![left, right.negate()]))
+    }
+
+    /// Evaluates an expression without variables straight to a number, skipping
+    /// the [`Value`] machinery. Returns `Ok(None)` if a variable shows up, in which
+    /// case [`Evaluator::evaluate_expression`] is needed instead.
+    pub fn evaluate_constant(&self, expression: &Expression) -> EvaluatorResult<Option<f64>> {
+        self.evaluate_with(expression, &HashMap::new())
+    }
+
+    /// Like [`Evaluator::evaluate_constant`], replacing each variable with its
+    /// value in `bindings`. Returns `Ok(None)` if a variable has no binding.
+    pub fn evaluate_with(
+        &self,
+        expression: &Expression,
+        bindings: &HashMap<String, f64>,
+    ) -> EvaluatorResult<Option<f64>> {
+        self.nested(expression, || {
+            self.evaluate_constant_node(expression, bindings)
+        })
+    }
+
+    /// Runs `evaluate` one level deeper, failing once the depth limit is reached.
+    fn nested<T>(
+        &self,
+        expression: &Expression,
+        evaluate: impl FnOnce() -> EvaluatorResult<T>,
+    ) -> EvaluatorResult<T> {
+        let depth = self.depth.get();
+        if depth >= self.max_depth {
+            return Err(EvaluatorError {
+                error_type: EvaluatorErrorType::RecursionLimit,
+                token: expression.token.clone(),
+                side: None,
+                expression: None,
+            });
+        }
+
+        self.depth.set(depth + 1);
+        let value = evaluate().map_err(|e| e.in_expression(expression));
+        self.depth.set(depth);
+        value
+    }
+
+    fn evaluate_constant_node(
+        &self,
+        expression: &Expression,
+        bindings: &HashMap<String, f64>,
+    ) -> EvaluatorResult<Option<f64>> {
+        let value = match &expression.expression_type {
+            ExpressionType::Number(num) => *num,
+            ExpressionType::Variable(name) => return Ok(bindings.get(name).copied()),
+            ExpressionType::Grouping(inner) => return self.evaluate_with(inner, bindings),
+            ExpressionType::Negation(inner) => {
+                return Ok(self.evaluate_with(inner, bindings)?.map(|v| -v));
+            }
+            ExpressionType::FunctionCall { name, parameter } => {
+                let Some(argument) = self.evaluate_with(parameter, bindings)? else {
+                    return Ok(None);
+                };
+
+                match self.functions.get(name) {
+                    Some(function) => function(argument),
+                    None => {
+                        return Err(EvaluatorError {
+                            error_type: EvaluatorErrorType::UndefinedFunction,
+                            token: expression.token.clone(),
+                            side: None,
+                            expression: None,
+                        });
+                    }
+                }
+            }
+            ExpressionType::Binary { operator, .. } if is_additive(operator) => {
+                let (first, operands) = sum_chain(expression);
+
+                let mut sum = self.evaluate_with(first, bindings)?;
+                for (operator, operand) in operands {
+                    let operand = self.evaluate_with(operand, bindings)?;
+                    sum = match (sum, operand) {
+                        (Some(a), Some(b)) if *operator == TokenType::Plus => Some(a + b),
+                        (Some(a), Some(b)) => Some(a - b),
+                        _ => None,
+                    };
+                }
+
+                return Ok(sum);
+            }
+            ExpressionType::Binary {
+                left,
+                operator,
+                right,
+            } => {
+                let (Some(a), Some(b)) = (
+                    self.evaluate_with(left, bindings)?,
+                    self.evaluate_with(right, bindings)?,
+                ) else {
+                    return Ok(None);
+                };
+
+                match operator.token_type {
+                    TokenType::Star => a * b,
+                    TokenType::Slash => divide(a, b, &right.token)?,
+                    TokenType::Hat => finite(a.powf(b), &left.token)?,
+                    _ => {
+                        return Err(EvaluatorError {
+                            error_type: EvaluatorErrorType::InvalidBinaryOperator,
+                            token: operator.clone(),
+                            side: None,
+                            expression: None,
+                        });
+                    }
+                }
+            }
+        };
+
+        Ok(Some(value))
+    }
+
+    fn evaluate_node(&self, expression: &Expression) -> EvaluatorResult<Value> {
         match &expression.expression_type {
             ExpressionType::Number(num) => Ok(Value::new_constant(*num)),
 
@@ -48,13 +218,15 @@ impl Evaluator {
 
             ExpressionType::Grouping(expression) => self.evaluate_expression(expression),
 
+            ExpressionType::Binary { operator, .. } if is_additive(operator) => {
+                self.evaluate_sum(expression)
+            }
+
             ExpressionType::Binary {
                 left,
                 operator,
                 right,
             } => match operator.token_type {
-                TokenType::Plus => self.evaluate_addition(left, right),
-                TokenType::Minus => self.evaluate_subtraction(left, right),
                 TokenType::Star => self.evaluate_multiplication(left, right),
                 TokenType::Slash => self.evaluate_division(left, right),
                 TokenType::Hat => self.evaluate_exponent(left, right),
@@ -62,17 +234,111 @@ impl Evaluator {
                 _ => Err(EvaluatorError {
                     error_type: EvaluatorErrorType::InvalidBinaryOperator,
                     token: operator.clone(),
+                    side: None,
+                    expression: None,
                 }),
             },
 
             ExpressionType::FunctionCall { name, parameter } => {
-                self.evaluate_function_call(parameter, name)
+                self.evaluate_function_call(&expression.token, parameter, name)
+            }
+        }
+    }
+
+    /// Evaluates a linear expression using exact fractions instead of floats.
+    /// Returns `None` whenever an operation cannot be represented exactly, like a
+    /// function call or a non-integer exponent, when the expression is not
+    /// linear, or when it nests deeper than the depth limit.
+    pub fn evaluate_rational(
+        &self,
+        expression: &Expression,
+    ) -> Option<Vec<(Option<String>, Rational)>> {
+        self.nested(expression, || Ok(self.evaluate_rational_node(expression)))
+            .ok()
+            .flatten()
+    }
+
+    fn evaluate_rational_node(
+        &self,
+        expression: &Expression,
+    ) -> Option<Vec<(Option<String>, Rational)>> {
+        match &expression.expression_type {
+            ExpressionType::Number(num) => Some(vec![(None, Rational::from_f64(*num)?)]),
+
+            ExpressionType::Variable(varname) => {
+                Some(vec![(Some(varname.clone()), Rational::from_integer(1))])
+            }
+
+            ExpressionType::Grouping(expression) => self.evaluate_rational(expression),
+
+            ExpressionType::Negation(expression) => self
+                .evaluate_rational(expression)?
+                .into_iter()
+                .map(|(variable, coefficient)| Some((variable, coefficient.checked_neg()?)))
+                .collect(),
+
+            ExpressionType::Binary { operator, .. } if is_additive(operator) => {
+                let (first, operands) = sum_chain(expression);
+
+                let mut terms = self.evaluate_rational(first)?;
+                for (operator, operand) in operands {
+                    let operand = self.evaluate_rational(operand)?;
+                    match operator {
+                        TokenType::Plus => terms.extend(operand),
+                        _ => {
+                            for (variable, coefficient) in operand {
+                                terms.push((variable, coefficient.checked_neg()?));
+                            }
+                        }
+                    }
+                }
+
+                Some(terms)
+            }
+
+            ExpressionType::Binary {
+                left,
+                operator,
+                right,
+            } => {
+                let left = self.evaluate_rational(left)?;
+                let right = self.evaluate_rational(right)?;
+
+                match operator.token_type {
+                    TokenType::Star => {
+                        match (rational_constant(&left), rational_constant(&right)) {
+                            (Some(factor), _) => scale_rational_terms(right, factor),
+                            (_, Some(factor)) => scale_rational_terms(left, factor),
+                            (None, None) => None,
+                        }
+                    }
+                    TokenType::Slash => {
+                        let divisor = rational_constant(&right)?;
+                        let factor = Rational::from_integer(1).checked_div(divisor)?;
+                        scale_rational_terms(left, factor)
+                    }
+                    TokenType::Hat => {
+                        let base = rational_constant(&left)?;
+                        let exponent = rational_constant(&right)?;
+                        if !exponent.is_integer() {
+                            return None;
+                        }
+                        let exponent = i32::try_from(exponent.numerator()).ok()?;
+                        Some(vec![(None, base.checked_pow(exponent)?)])
+                    }
+                    _ => None,
+                }
             }
+
+            ExpressionType::FunctionCall { .. } => None,
         }
     }
 
+    /// Calls `function_name` on `expression`. `call` is the function name token,
+    /// reported when the function is undefined.
     fn evaluate_function_call(
         &self,
+        call: &Token,
         expression: &Expression,
         function_name: &str,
     ) -> EvaluatorResult<Value> {
@@ -84,18 +350,24 @@ impl Evaluator {
                 Some(_) => Err(EvaluatorError {
                     error_type: EvaluatorErrorType::ForbiddenParam,
                     token: expression.token.clone(),
+                    side: None,
+                    expression: None,
                 }),
                 None => match self.functions.get(function_name) {
                     Some(function) => Ok(Value::new_constant(function(coefficient))),
                     None => Err(EvaluatorError {
                         error_type: EvaluatorErrorType::UndefinedFunction,
-                        token: expression.token.clone(),
+                        token: call.clone(),
+                        side: None,
+                        expression: None,
                     }),
                 },
             },
             Value::Sum(_) => Err(EvaluatorError {
                 error_type: EvaluatorErrorType::ForbiddenParam,
                 token: expression.token.clone(),
+                side: None,
+                expression: None,
             }),
         }
     }
@@ -105,187 +377,39 @@ impl Evaluator {
         left: &Expression,
         exponent: &Expression,
     ) -> EvaluatorResult<Value> {
-        let left_result = self.evaluate_expression(left)?;
-        let exponent_value = self.evaluate_expression(exponent)?;
-
-        match (left_result, exponent_value) {
-            (
-                Value::Monomial {
-                    coefficient: c1,
-                    variable: v1,
-                },
-                Value::Monomial {
-                    coefficient: c2,
-                    variable: v2,
-                },
-            ) => match (v1, v2) {
-                (None, None) => Ok(Value::new_constant(c1.powf(c2))),
-                (Some(_), _) => Err(EvaluatorError {
-                    error_type: EvaluatorErrorType::NonConstantBase,
-                    token: left.token.clone(),
-                }),
-                (_, Some(_)) => Err(EvaluatorError {
-                    error_type: EvaluatorErrorType::NonConstantExponent,
-                    token: exponent.token.clone(),
-                }),
-            },
-
-            _ => Err(EvaluatorError {
+        let Some(base) = self.evaluate_expression(left)?.as_constant() else {
+            return Err(EvaluatorError {
+                error_type: EvaluatorErrorType::NonConstantBase,
+                token: left.token.clone(),
+                side: None,
+                expression: None,
+            });
+        };
+        let Some(power) = self.evaluate_expression(exponent)?.as_constant() else {
+            return Err(EvaluatorError {
                 error_type: EvaluatorErrorType::NonConstantExponent,
                 token: exponent.token.clone(),
-            }),
-        }
-    }
-
-    fn evaluate_addition(&self, left: &Expression, right: &Expression) -> EvaluatorResult<Value> {
-        let left = self.evaluate_expression(left)?;
-        let right = self.evaluate_expression(right)?;
-
-        match (left, right) {
-            (
-                Value::Monomial {
-                    coefficient: c1,
-                    variable: v1,
-                },
-                Value::Monomial {
-                    coefficient: c2,
-                    variable: v2,
-                },
-            ) => {
-                let value = match (v1, v2) {
-                    (Option::None, Option::None) => Value::new_constant(c1 + c2),
-                    (Some(v1), Some(v2)) if v1 == v2 => Value::new_monomial(c1 + c2, v1),
-                    (Some(v), Option::None) => {
-                        let left = Value::new_monomial(c1, v);
-                        let right = Value::new_constant(c2);
-                        Value::Sum(vec![left, right])
-                    }
-                    (Option::None, Some(v)) => {
-                        let left = Value::new_constant(c1);
-                        let right = Value::new_monomial(c2, v);
-                        Value::Sum(vec![left, right])
-                    }
-                    (Some(v1), Some(v2)) => {
-                        let left = Value::new_monomial(c1, v1);
-                        let right = Value::new_monomial(c2, v2);
-                        Value::Sum(vec![left, right])
-                    }
-                };
+                side: None,
+                expression: None,
+            });
+        };
 
-                Ok(value)
-            }
-            (
-                Value::Sum(mut values),
-                Value::Monomial {
-                    coefficient,
-                    variable,
-                },
-            ) => {
-                values.push(Value::Monomial {
-                    coefficient,
-                    variable,
-                });
-                Ok(Value::Sum(values))
-            }
-            (
-                Value::Monomial {
-                    coefficient,
-                    variable,
-                },
-                Value::Sum(mut values),
-            ) => {
-                values.push(Value::Monomial {
-                    coefficient,
-                    variable,
-                });
-                Ok(Value::Sum(values))
-            }
-            (Value::Sum(mut left_sum), Value::Sum(mut right_sum)) => {
-                left_sum.append(&mut right_sum);
-                Ok(Value::Sum(left_sum))
-            }
-        }
+        Ok(Value::new_constant(finite(base.powf(power), &left.token)?))
     }
 
-    fn evaluate_subtraction(
-        &self,
-        left: &Expression,
-        right: &Expression,
-    ) -> EvaluatorResult<Value> {
-        let left = self.evaluate_expression(left)?;
-        let right = self.evaluate_expression(right)?;
-
-        match (left, right) {
-            (
-                Value::Monomial {
-                    coefficient: c1,
-                    variable: v1,
-                },
-                Value::Monomial {
-                    coefficient: c2,
-                    variable: v2,
-                },
-            ) => {
-                let value = match (v1, v2) {
-                    (Option::None, Option::None) => Value::new_constant(c1 - c2),
-                    (Option::None, Some(v)) => {
-                        let left = Value::new_constant(c1);
-                        let right = Value::new_monomial(-c2, v);
-                        Value::Sum(vec![left, right])
-                    }
-                    (Some(v), Option::None) => {
-                        let left = Value::new_monomial(c1, v);
-                        let right = Value::new_constant(-c2);
-                        Value::Sum(vec![left, right])
-                    }
-                    (Some(v1), Some(v2)) if v1 == v2 => Value::new_monomial(c1 - c2, v1),
-                    (Some(v1), Some(v2)) => {
-                        let left = Value::new_monomial(c1, v1);
-                        let right = Value::new_monomial(-c2, v2);
-                        Value::Sum(vec![left, right])
-                    }
-                };
-
-                Ok(value)
-            }
-            (
-                Value::Sum(mut values),
-                Value::Monomial {
-                    coefficient,
-                    variable,
-                },
-            ) => {
-                values.push(Value::Monomial {
-                    coefficient: -coefficient,
-                    variable,
-                });
-                Ok(Value::Sum(values))
-            }
-            (
-                Value::Monomial {
-                    coefficient,
-                    variable,
-                },
-                Value::Sum(values),
-            ) => {
-                let mut values_result = Vec::new();
+    fn evaluate_sum(&self, expression: &Expression) -> EvaluatorResult<Value> {
+        let (first, operands) = sum_chain(expression);
 
-                values_result.push(Value::Monomial {
-                    coefficient,
-                    variable,
-                });
-
-                for value in values.into_iter() {
-                    let value = value.negate();
-                    values_result.push(value);
-                }
-                Ok(Value::Sum(values_result))
-            }
-            (Value::Sum(mut left_sum), Value::Sum(mut right_sum)) => {
-                left_sum.append(&mut right_sum);
-                Ok(Value::Sum(left_sum))
-            }
+        let mut value = self.evaluate_expression(first)?;
+        for (operator, operand) in operands {
+            let operand = self.evaluate_expression(operand)?;
+            value = match operator {
+                TokenType::Plus => add_values(value, operand),
+                _ => subtract_values(value, operand),
+            };
         }
+
+        Ok(value)
     }
 
     fn evaluate_multiplication(
@@ -317,10 +441,12 @@ impl Evaluator {
                 }),
                 (Some(_), Some(_)) => Err(EvaluatorError {
                     error_type: EvaluatorErrorType::VariableMultiplication {
-                        left: left.token.clone(),
-                        right: right.token.clone(),
+                        left: Box::new(left.token.clone()),
+                        right: Box::new(right.token.clone()),
                     },
                     token: left.token.clone(),
+                    side: None,
+                    expression: None,
                 }),
             },
             (value_a, value_b) => {
@@ -353,7 +479,7 @@ impl Evaluator {
                     &right.token,
                 )?;
 
-                Ok(Value::Sum(values))
+                Ok(combine_like_terms(values))
             }
         }
     }
@@ -365,6 +491,17 @@ impl Evaluator {
         left_token: &Token,
         right_token: &Token,
     ) -> EvaluatorResult<Vec<Value>> {
+        if let Some(max_terms) = self.max_terms
+            && left.len().saturating_mul(right.len()) > max_terms
+        {
+            return Err(EvaluatorError {
+                error_type: EvaluatorErrorType::TermLimitExceeded,
+                token: left_token.clone(),
+                side: None,
+                expression: None,
+            });
+        }
+
         let mut result = Vec::new();
         for left_value in left {
             for right_value in right {
@@ -389,10 +526,12 @@ impl Evaluator {
                         }),
                         (Some(_), Some(_)) => Err(EvaluatorError {
                             error_type: EvaluatorErrorType::VariableMultiplication {
-                                left: left_token.clone(),
-                                right: right_token.clone(),
+                                left: Box::new(left_token.clone()),
+                                right: Box::new(right_token.clone()),
                             },
                             token: left_token.clone(),
+                            side: None,
+                            expression: None,
                         }),
                     },
                     (
@@ -452,23 +591,30 @@ impl Evaluator {
                     coefficient: c2,
                     variable: v2,
                 },
-            ) => match (v1, v2) {
-                (Option::None, Option::None) => Ok(Value::Monomial {
-                    coefficient: c1 / c2,
-                    variable: None,
-                }),
-                (_, Some(_)) => Err(EvaluatorError {
-                    error_type: EvaluatorErrorType::VariableDivision {
-                        numerator: left.token.clone(),
-                        denominator: right.token.clone(),
-                    },
-                    token: left.token.clone(),
-                }),
-                (Some(v), Option::None) => Ok(Value::Monomial {
-                    coefficient: c1 / c2,
-                    variable: Some(v.clone()),
-                }),
-            },
+            ) => {
+                let quotient = divide(c1, c2, &right.token)?;
+
+                match (v1, v2) {
+                    (Option::None, Option::None) => Ok(Value::Monomial {
+                        coefficient: quotient,
+                        variable: None,
+                    }),
+                    (Some(v1), Some(v2)) if v1 == v2 => Ok(Value::new_constant(quotient)),
+                    (_, Some(_)) => Err(EvaluatorError {
+                        error_type: EvaluatorErrorType::VariableDivision {
+                            numerator: Box::new(left.token.clone()),
+                            denominator: Box::new(right.token.clone()),
+                        },
+                        token: left.token.clone(),
+                        side: None,
+                        expression: None,
+                    }),
+                    (Some(v), Option::None) => Ok(Value::Monomial {
+                        coefficient: quotient,
+                        variable: Some(v.clone()),
+                    }),
+                }
+            }
             (value_a, value_b) => {
                 let left_values = match value_a {
                     Value::Sum(sum) => sum,
@@ -524,23 +670,31 @@ impl Evaluator {
                             coefficient: c2,
                             variable: v2,
                         },
-                    ) => match (v1, v2) {
-                        (Option::None, Option::None) => Ok(Value::Monomial {
-                            coefficient: c1 / c2,
-                            variable: None,
-                        }),
-                        (Some(v), Option::None) | (Option::None, Some(v)) => Ok(Value::Monomial {
-                            coefficient: c1 / c2,
-                            variable: Some(v.clone()),
-                        }),
-                        (Some(_), Some(_)) => Err(EvaluatorError {
-                            error_type: EvaluatorErrorType::VariableMultiplication {
-                                left: left_token.clone(),
-                                right: right_token.clone(),
-                            },
-                            token: left_token.clone(),
-                        }),
-                    },
+                    ) => {
+                        let quotient = divide(*c1, *c2, right_token)?;
+
+                        match (v1, v2) {
+                            (Option::None, Option::None) => Ok(Value::Monomial {
+                                coefficient: quotient,
+                                variable: None,
+                            }),
+                            (Some(v), Option::None) => Ok(Value::Monomial {
+                                coefficient: quotient,
+                                variable: Some(v.clone()),
+                            }),
+                            // Cancelling term by term would turn `x/(x + 1)` into
+                            // `1 + x`, so only whole monomials cancel a variable.
+                            (_, Some(_)) => Err(EvaluatorError {
+                                error_type: EvaluatorErrorType::VariableDivision {
+                                    numerator: Box::new(left_token.clone()),
+                                    denominator: Box::new(right_token.clone()),
+                                },
+                                token: left_token.clone(),
+                                side: None,
+                                expression: None,
+                            }),
+                        }
+                    }
                     (
                         Value::Monomial {
                             coefficient,
@@ -585,11 +739,313 @@ impl Evaluator {
     }
 }
 
+impl Equation {
+    /// Whether substituting `bindings` into both sides, as written, satisfies the
+    /// relation, with equality allowed to be off by up to `epsilon`. An equation
+    /// with a variable missing from `bindings` is never satisfied.
+    pub fn is_satisfied_by(
+        &self,
+        bindings: &HashMap<String, f64>,
+        epsilon: f64,
+    ) -> EvaluatorResult<bool> {
+        let evaluator = Evaluator::new();
+        let left = evaluator
+            .evaluate_with(&self.left, bindings)
+            .map_err(|e| e.on_side(EquationSide::Left))?;
+        let right = evaluator
+            .evaluate_with(&self.right, bindings)
+            .map_err(|e| e.on_side(EquationSide::Right))?;
+        let (Some(left), Some(right)) = (left, right) else {
+            return Ok(false);
+        };
+
+        Ok(match self.relation {
+            Relation::Eq => (left - right).abs() <= epsilon,
+            Relation::Le => left <= right + epsilon,
+            Relation::Ge => left + epsilon >= right,
+            Relation::Lt => left < right,
+            Relation::Gt => left > right,
+        })
+    }
+}
+
+fn finite(value: f64, token: &Token) -> EvaluatorResult<f64> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(EvaluatorError {
+            error_type: EvaluatorErrorType::NumericOverflow,
+            token: token.clone(),
+            side: None,
+            expression: None,
+        })
+    }
+}
+
+fn divide(numerator: f64, denominator: f64, token: &Token) -> EvaluatorResult<f64> {
+    if denominator == 0.0 {
+        return Err(EvaluatorError {
+            error_type: EvaluatorErrorType::ZeroDivision,
+            token: token.clone(),
+            side: None,
+            expression: None,
+        });
+    }
+
+    finite(numerator / denominator, token)
+}
+
+/// Splits a chain of additions and subtractions like `a + b - c`, which parses
+/// left-nested as `(a + b) - c`, into its first operand and each following
+/// operand with its operator. Evaluating the chain in a loop keeps long sums
+/// from recursing once per term.
+fn sum_chain(expression: &Expression) -> (&Expression, Vec<(&TokenType, &Expression)>) {
+    let mut first = expression;
+    let mut operands = Vec::new();
+    while let ExpressionType::Binary {
+        left,
+        operator,
+        right,
+    } = &first.expression_type
+        && is_additive(operator)
+    {
+        operands.push((&operator.token_type, right.as_ref()));
+        first = left;
+    }
+    operands.reverse();
+
+    (first, operands)
+}
+
+fn is_additive(operator: &Token) -> bool {
+    matches!(operator.token_type, TokenType::Plus | TokenType::Minus)
+}
+
+/// `left + right`, keeping a sum's like terms combined.
+fn add_values(left: Value, right: Value) -> Value {
+    match (left, right) {
+        (
+            Value::Monomial {
+                coefficient: c1,
+                variable: v1,
+            },
+            Value::Monomial {
+                coefficient: c2,
+                variable: v2,
+            },
+        ) => match (v1, v2) {
+            (Option::None, Option::None) => Value::new_constant(c1 + c2),
+            (Some(v1), Some(v2)) if v1 == v2 => nonzero_monomial(c1 + c2, v1),
+            (Some(v), Option::None) => {
+                let left = Value::new_monomial(c1, v);
+                let right = Value::new_constant(c2);
+                Value::Sum(vec![left, right])
+            }
+            (Option::None, Some(v)) => {
+                let left = Value::new_constant(c1);
+                let right = Value::new_monomial(c2, v);
+                Value::Sum(vec![left, right])
+            }
+            (Some(v1), Some(v2)) => {
+                let left = Value::new_monomial(c1, v1);
+                let right = Value::new_monomial(c2, v2);
+                Value::Sum(vec![left, right])
+            }
+        },
+        (
+            Value::Sum(mut values),
+            Value::Monomial {
+                coefficient,
+                variable,
+            },
+        )
+        | (
+            Value::Monomial {
+                coefficient,
+                variable,
+            },
+            Value::Sum(mut values),
+        ) => {
+            add_like_term(&mut values, coefficient, variable);
+            Value::Sum(values)
+        }
+        (Value::Sum(mut left_sum), Value::Sum(mut right_sum)) => {
+            left_sum.append(&mut right_sum);
+            Value::Sum(left_sum)
+        }
+    }
+}
+
+/// `left - right`.
+fn subtract_values(left: Value, right: Value) -> Value {
+    match (left, right) {
+        (
+            Value::Monomial {
+                coefficient: c1,
+                variable: v1,
+            },
+            Value::Monomial {
+                coefficient: c2,
+                variable: v2,
+            },
+        ) => match (v1, v2) {
+            (Option::None, Option::None) => Value::new_constant(c1 - c2),
+            (Option::None, Some(v)) => {
+                let left = Value::new_constant(c1);
+                let right = Value::new_monomial(-c2, v);
+                Value::Sum(vec![left, right])
+            }
+            (Some(v), Option::None) => {
+                let left = Value::new_monomial(c1, v);
+                let right = Value::new_constant(-c2);
+                Value::Sum(vec![left, right])
+            }
+            (Some(v1), Some(v2)) if v1 == v2 => nonzero_monomial(c1 - c2, v1),
+            (Some(v1), Some(v2)) => {
+                let left = Value::new_monomial(c1, v1);
+                let right = Value::new_monomial(-c2, v2);
+                Value::Sum(vec![left, right])
+            }
+        },
+        (
+            Value::Sum(mut values),
+            Value::Monomial {
+                coefficient,
+                variable,
+            },
+        ) => {
+            values.push(Value::Monomial {
+                coefficient: -coefficient,
+                variable,
+            });
+            Value::Sum(values)
+        }
+        (
+            Value::Monomial {
+                coefficient,
+                variable,
+            },
+            Value::Sum(values),
+        ) => {
+            let mut values_result = Vec::new();
+
+            values_result.push(Value::Monomial {
+                coefficient,
+                variable,
+            });
+
+            for value in values.into_iter() {
+                let value = value.negate();
+                values_result.push(value);
+            }
+            Value::Sum(values_result)
+        }
+        (Value::Sum(mut left_sum), Value::Sum(right_sum)) => {
+            left_sum.extend(right_sum.into_iter().map(Value::negate));
+            Value::Sum(left_sum)
+        }
+    }
+}
+
+/// Adds a monomial to a sum, merging it into the term with the same variable if
+/// there is one.
+fn add_like_term(values: &mut Vec<Value>, coefficient: f64, variable: Option<String>) {
+    let existing = values.iter_mut().find_map(|value| match value {
+        Value::Monomial {
+            coefficient,
+            variable: v,
+        } if *v == variable => Some(coefficient),
+        _ => None,
+    });
+
+    match existing {
+        Some(existing) => *existing += coefficient,
+        None => values.push(Value::Monomial {
+            coefficient,
+            variable,
+        }),
+    }
+}
+
+/// Flattens nested sums and adds up the coefficients of repeated variables and
+/// constants, keeping the order in which each one first appears. Terms that
+/// cancel are dropped, leaving [`Value::zero`] if nothing else remains, and a
+/// single remaining term is not wrapped in a sum. Distributing `(a + b)(c + d)`
+/// produces nested, repeated terms that this folds back.
+fn combine_like_terms(values: Vec<Value>) -> Value {
+    let mut combined: Vec<(Option<String>, f64)> = Vec::new();
+    let mut pending = values;
+    pending.reverse();
+
+    while let Some(value) = pending.pop() {
+        match value {
+            Value::Sum(mut values) => {
+                values.reverse();
+                pending.append(&mut values);
+            }
+            Value::Monomial {
+                coefficient,
+                variable,
+            } => match combined.iter_mut().find(|(name, _)| *name == variable) {
+                Some((_, total)) => *total += coefficient,
+                None => combined.push((variable, coefficient)),
+            },
+        }
+    }
+
+    let mut terms: Vec<Value> = combined
+        .into_iter()
+        .filter(|(_, coefficient)| *coefficient != 0.0)
+        .map(|(variable, coefficient)| Value::Monomial {
+            coefficient,
+            variable,
+        })
+        .collect();
+
+    match terms.len() {
+        0 => Value::zero(),
+        1 => terms.pop().unwrap(),
+        _ => Value::Sum(terms),
+    }
+}
+
+/// A monomial of `variable`, or [`Value::zero`] when the coefficient cancelled.
+fn nonzero_monomial(coefficient: f64, variable: String) -> Value {
+    if coefficient == 0.0 {
+        Value::zero()
+    } else {
+        Value::new_monomial(coefficient, variable)
+    }
+}
+
+fn rational_constant(terms: &[(Option<String>, Rational)]) -> Option<Rational> {
+    terms.iter().try_fold(
+        Rational::from_integer(0),
+        |sum, (variable, coefficient)| match variable {
+            Some(_) => None,
+            None => sum.checked_add(*coefficient),
+        },
+    )
+}
+
+fn scale_rational_terms(
+    terms: Vec<(Option<String>, Rational)>,
+    factor: Rational,
+) -> Option<Vec<(Option<String>, Rational)>> {
+    terms
+        .into_iter()
+        .map(|(variable, coefficient)| Some((variable, coefficient.checked_mul(factor)?)))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::evaluator::error::EvaluatorErrorType;
     use crate::evaluator::value::Value;
     use crate::lexer::{Equation, Lexer};
+    use crate::rational::Rational;
     use crate::tokenizer::{Token, Tokenizer};
+    use std::collections::HashMap;
     use std::io::{BufReader, Cursor};
 
     #[cfg(test)]
@@ -812,6 +1268,28 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_chained_group_products_are_flat() {
+        let equation = equation_from_text("(1 + 6)(x + 9 + x)(2 + 1) = (1 + 6)(x + 9)(y - 2)");
+        let evaluator = super::Evaluator::new();
+
+        let left = evaluator.evaluate_expression(&equation.left).unwrap();
+        assert_eq!(
+            left,
+            Value::Sum(vec![
+                Value::new_monomial(42.0, String::from("x")),
+                Value::new_constant(189.0),
+            ])
+        );
+
+        // Expanding `(x + 9)(y - 2)` would need an `xy` term, which is not linear.
+        let error = evaluator.evaluate_expression(&equation.right).unwrap_err();
+        assert!(matches!(
+            error.error_type,
+            EvaluatorErrorType::VariableMultiplication { .. }
+        ));
+    }
+
     #[test]
     #[should_panic]
     fn test_invalid_group_multiplication_left() {
@@ -945,6 +1423,44 @@ mod tests {
         assert_eq!(right, Value::new_constant(2.0));
     }
 
+    #[test]
+    fn test_builtin_calls_without_backslash() {
+        let evaluator = super::Evaluator::new();
+        for name in crate::lexer::BUILTIN_FUNCTIONS {
+            assert!(
+                evaluator.functions.contains_key(name),
+                "{name} is undefined"
+            );
+        }
+
+        let equation = equation_from_text("sqrt(4)x = 1");
+        assert_eq!(
+            evaluator.evaluate_expression(&equation.left).unwrap(),
+            Value::new_monomial(2.0, String::from("x"))
+        );
+    }
+
+    #[test]
+    fn test_undefined_function_token() {
+        let equation = equation_from_text("\\foo(1 + 2) = 1");
+        let evaluator = super::Evaluator::new();
+
+        let fast = evaluator.evaluate_constant(&equation.left).unwrap_err();
+        let slow = evaluator.evaluate_expression(&equation.left).unwrap_err();
+
+        assert!(matches!(
+            fast.error_type,
+            EvaluatorErrorType::UndefinedFunction
+        ));
+        assert_eq!(fast.token, equation.left.token);
+        assert_eq!(fast.token, slow.token);
+        assert_eq!(fast.to_string(), slow.to_string());
+        assert!(
+            fast.to_string()
+                .starts_with("Function \\foo is undefined. Found in column 1")
+        );
+    }
+
     #[test]
     fn test_function_with_sum_mult() {
         let equation = equation_from_text("\\sin(9 + 2 * 2 - 13) = \\sqrt(9/2 + 1 - 5.5 + 4)");
@@ -979,4 +1495,411 @@ mod tests {
 
         evaluator.evaluate_expression(&equation.left).unwrap();
     }
+
+    #[test]
+    fn test_rational_evaluation() {
+        let equation = equation_from_text("(1/3)x + 0.5 = 2^(0 - 2) * (y - 1)");
+        let evaluator = super::Evaluator::new();
+
+        let left = evaluator.evaluate_rational(&equation.left).unwrap();
+        let right = evaluator.evaluate_rational(&equation.right).unwrap();
+
+        assert_eq!(
+            left,
+            vec![
+                (Some(String::from("x")), Rational::new(1, 3).unwrap()),
+                (None, Rational::new(1, 2).unwrap()),
+            ]
+        );
+        assert_eq!(
+            right,
+            vec![
+                (Some(String::from("y")), Rational::new(1, 4).unwrap()),
+                (None, Rational::new(-1, 4).unwrap()),
+            ]
+        );
+
+        let equation = equation_from_text("x\\sqrt(2) = 4^0.5");
+        assert_eq!(evaluator.evaluate_rational(&equation.left), None);
+        assert_eq!(evaluator.evaluate_rational(&equation.right), None);
+    }
+
+    #[test]
+    fn test_same_variable_division() {
+        let equation = equation_from_text("x/x = 6x/(2x)");
+        let evaluator = super::Evaluator::new();
+
+        let left = evaluator.evaluate_expression(&equation.left).unwrap();
+        let right = evaluator.evaluate_expression(&equation.right).unwrap();
+
+        assert_eq!(left, Value::new_constant(1.0));
+        assert_eq!(right, Value::new_constant(3.0));
+    }
+
+    #[test]
+    fn test_variable_sum_division() {
+        let evaluator = super::Evaluator::new();
+
+        for text in ["x/(x+1) = 0", "x/(x+y) = 0", "(x+1)/(x+1) = 0"] {
+            let equation = equation_from_text(text);
+            let error = evaluator.evaluate_expression(&equation.left).unwrap_err();
+            assert!(
+                matches!(
+                    error.error_type,
+                    EvaluatorErrorType::VariableDivision { .. }
+                ),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_on_sum_divided_by_variable() {
+        let equation = equation_from_text("(x + 1)/x = 2");
+        let evaluator = super::Evaluator::new();
+
+        evaluator.evaluate_expression(&equation.left).unwrap();
+    }
+
+    #[test]
+    fn test_negated_exponentiation() {
+        let equation = equation_from_text("-2^2 = 3 - 2^2");
+        let evaluator = super::Evaluator::new();
+
+        let left = evaluator.evaluate_expression(&equation.left).unwrap();
+        let right = evaluator.evaluate_expression(&equation.right).unwrap();
+
+        assert_eq!(left, Value::new_constant(-4.0));
+        assert_eq!(right, Value::new_constant(-1.0));
+    }
+
+    #[test]
+    fn test_numeric_overflow() {
+        let evaluator = super::Evaluator::new();
+
+        let equation = equation_from_text("9^99999 = 1");
+        let error = evaluator.evaluate_expression(&equation.left).unwrap_err();
+        assert!(matches!(
+            error.error_type,
+            EvaluatorErrorType::NumericOverflow
+        ));
+
+        let equation = equation_from_text("x = (10^300)x / 10^(0 - 300)");
+        let error = evaluator.evaluate_expression(&equation.right).unwrap_err();
+        assert!(matches!(
+            error.error_type,
+            EvaluatorErrorType::NumericOverflow
+        ));
+    }
+
+    #[test]
+    fn test_zero_division() {
+        let evaluator = super::Evaluator::new();
+
+        let equation = equation_from_text("(x + 1) / (2 - 2) = 1");
+        let error = evaluator.evaluate_expression(&equation.left).unwrap_err();
+        assert!(matches!(error.error_type, EvaluatorErrorType::ZeroDivision));
+    }
+
+    #[test]
+    fn test_recursion_limit() {
+        let nested = format!("{}x{} = 1", "(".repeat(60), ")".repeat(60));
+        let equation = equation_from_text(&nested);
+
+        let evaluator = super::Evaluator::new().with_max_depth(50);
+        match evaluator.evaluate_expression(&equation.left) {
+            Err(error) => assert!(matches!(
+                error.error_type,
+                EvaluatorErrorType::RecursionLimit
+            )),
+            Ok(value) => panic!("Expected a recursion error, found {value:?}"),
+        }
+        assert_eq!(evaluator.evaluate_rational(&equation.left), None);
+
+        // A failed evaluation must not leave the depth counter behind.
+        let equation = equation_from_text("x + x = 1");
+        assert!(evaluator.evaluate_expression(&equation.left).is_ok());
+    }
+
+    #[test]
+    fn test_long_sums_are_not_nested() {
+        let evaluator = super::Evaluator::new().with_max_depth(10);
+
+        let terms = vec!["x"; 500].join(" + ");
+        let equation = equation_from_text(&format!("{terms} - 2x = 1"));
+        assert_eq!(
+            evaluator.evaluate_expression(&equation.left).unwrap(),
+            Value::new_monomial(498.0, String::from("x"))
+        );
+        let mut rational = vec![(Some(String::from("x")), Rational::from_integer(1)); 500];
+        rational.push((Some(String::from("x")), Rational::from_integer(-2)));
+        assert_eq!(evaluator.evaluate_rational(&equation.left), Some(rational));
+
+        let terms = vec!["1"; 500].join(" + ");
+        let equation = equation_from_text(&format!("{terms} - 2 = x"));
+        assert_eq!(
+            evaluator.evaluate_constant(&equation.left).unwrap(),
+            Some(498.0)
+        );
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let equation = equation_from_text("x/10 + 2x/10 + 1 = 1 + 0.3x");
+        let evaluator = super::Evaluator::new();
+
+        let left = evaluator.evaluate_expression(&equation.left).unwrap();
+        let right = evaluator.evaluate_expression(&equation.right).unwrap();
+
+        assert_ne!(left, right);
+        assert!(left.approx_eq(&right, 1e-12));
+        assert!(!left.approx_eq(&Value::new_monomial(0.3, String::from("x")), 1e-12));
+        assert!(Value::new_constant(0.0).approx_eq(&Value::Sum(vec![]), 0.0));
+    }
+
+    #[test]
+    fn test_evaluate_constant() {
+        let equation = equation_from_text("2^3 - \\sqrt(16)/(1 + 1) = 2 + x");
+        let evaluator = super::Evaluator::new();
+
+        assert_eq!(
+            evaluator.evaluate_constant(&equation.left).unwrap(),
+            Some(6.0)
+        );
+        assert_eq!(evaluator.evaluate_constant(&equation.right).unwrap(), None);
+
+        let equation = equation_from_text("1/(2 - 2) = 1");
+        let error = evaluator.evaluate_constant(&equation.left).unwrap_err();
+        assert!(matches!(error.error_type, EvaluatorErrorType::ZeroDivision));
+    }
+
+    #[test]
+    fn test_term_limit() {
+        let equation = equation_from_text("(x + 1 + y + 2z + w)*3 = 0");
+
+        let evaluator = super::Evaluator::new().with_max_terms(5);
+        assert!(evaluator.evaluate_expression(&equation.left).is_ok());
+
+        let evaluator = super::Evaluator::new().with_max_terms(4);
+        let error = evaluator.evaluate_expression(&equation.left).unwrap_err();
+        assert!(matches!(
+            error.error_type,
+            EvaluatorErrorType::TermLimitExceeded
+        ));
+    }
+
+    #[test]
+    fn test_evaluate_equation() {
+        let equation = equation_from_text("2x + 3 = x + 5");
+        let value = super::Evaluator::new()
+            .evaluate_equation(&equation)
+            .unwrap();
+
+        assert_eq!(
+            value,
+            Value::Sum(vec![
+                Value::new_monomial(1.0, String::from("x")),
+                Value::new_constant(-2.0),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_sum_minus_sum() {
+        let equation = equation_from_text("(x + 1) - (y + 2) = 0");
+        let value = super::Evaluator::new()
+            .evaluate_expression(&equation.left)
+            .unwrap();
+
+        assert_eq!(
+            value,
+            Value::Sum(vec![
+                Value::new_monomial(1.0, String::from("x")),
+                Value::new_constant(1.0),
+                Value::new_monomial(-1.0, String::from("y")),
+                Value::new_constant(-2.0),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_absolute_value_bars() {
+        let equation = equation_from_text("|2 - 5| = 0");
+        let value = super::Evaluator::new()
+            .evaluate_expression(&equation.left)
+            .unwrap();
+        assert_eq!(value, Value::new_constant(3.0));
+
+        let equation = equation_from_text("|x - 3| = 0");
+        let error = super::Evaluator::new()
+            .evaluate_expression(&equation.left)
+            .unwrap_err();
+        assert!(matches!(
+            error.error_type,
+            EvaluatorErrorType::ForbiddenParam
+        ));
+    }
+
+    #[test]
+    fn test_addition_combines_like_terms() {
+        let equation = equation_from_text("x + x + x = x + y + x");
+        let evaluator = super::Evaluator::new();
+
+        assert_eq!(
+            evaluator.evaluate_expression(&equation.left).unwrap(),
+            Value::new_monomial(3.0, String::from("x"))
+        );
+        assert_eq!(
+            evaluator.evaluate_expression(&equation.right).unwrap(),
+            Value::Sum(vec![
+                Value::new_monomial(2.0, String::from("x")),
+                Value::new_monomial(1.0, String::from("y")),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_grouped_constant_base() {
+        let equation = equation_from_text("(2+3)^2 = 25");
+        let evaluator = super::Evaluator::new();
+
+        assert_eq!(
+            evaluator.evaluate_expression(&equation.left).unwrap(),
+            Value::new_constant(25.0)
+        );
+        assert_eq!(
+            evaluator.evaluate_constant(&equation.left).unwrap(),
+            Some(25.0)
+        );
+
+        let equation = equation_from_text("(1 + 1)^(1 + 2) * x = 0");
+        assert_eq!(
+            evaluator.evaluate_expression(&equation.left).unwrap(),
+            Value::new_monomial(8.0, String::from("x"))
+        );
+    }
+
+    #[test]
+    fn test_exponent_requires_constants() {
+        let evaluator = super::Evaluator::new();
+        let equation = equation_from_text("(x + 1)^2 = 2^(y - 1)");
+
+        let error = evaluator.evaluate_expression(&equation.left).unwrap_err();
+        assert!(matches!(
+            error.error_type,
+            EvaluatorErrorType::NonConstantBase
+        ));
+        let error = evaluator.evaluate_expression(&equation.right).unwrap_err();
+        assert!(matches!(
+            error.error_type,
+            EvaluatorErrorType::NonConstantExponent
+        ));
+    }
+
+    #[test]
+    fn test_cancelling_terms_are_zero() {
+        let evaluator = super::Evaluator::new();
+
+        let equation = equation_from_text("x - x = 2x + 3 - 2x - 3");
+        let left = evaluator.evaluate_expression(&equation.left).unwrap();
+        assert_eq!(left, Value::zero());
+        assert_eq!(left.to_string(), "0");
+        assert!(
+            evaluator
+                .evaluate_expression(&equation.right)
+                .unwrap()
+                .is_zero()
+        );
+
+        let equation = equation_from_text("x + 1 = x + 1");
+        let value = evaluator.evaluate_equation(&equation).unwrap();
+        assert_eq!(value, Value::zero());
+        assert_eq!(value.to_string(), "0");
+
+        let equation = equation_from_text("2x + 1 = x + 1");
+        let value = evaluator.evaluate_equation(&equation).unwrap();
+        assert_eq!(value, Value::new_monomial(1.0, String::from("x")));
+
+        let equation = equation_from_text("0(x + 1) = 0");
+        let value = evaluator.evaluate_expression(&equation.left).unwrap();
+        assert_eq!(value, Value::zero());
+    }
+
+    #[test]
+    fn test_negative_base() {
+        let evaluator = super::Evaluator::new();
+        let cases = [("(-2)^2", 4.0), ("(-2)^3", -8.0), ("-2^2", -4.0)];
+
+        for (text, expected) in cases {
+            let equation = equation_from_text(&format!("{text} = 0"));
+            assert_eq!(
+                evaluator.evaluate_expression(&equation.left).unwrap(),
+                Value::new_constant(expected),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_implicit_multiplication_error_column() {
+        // The error points at the product on the left, where its `*` would go.
+        let equation = equation_from_text("2 x * 3 y = 1");
+        let error = super::Evaluator::new()
+            .evaluate_expression(&equation.left)
+            .unwrap_err();
+
+        assert!(matches!(
+            error.error_type,
+            EvaluatorErrorType::VariableMultiplication { .. }
+        ));
+        assert_eq!(error.token.column, 2);
+    }
+
+    #[test]
+    fn test_is_satisfied_by() {
+        let equation = equation_from_text("2x + y = 5");
+        let bindings =
+            |x: f64, y: f64| HashMap::from([(String::from("x"), x), (String::from("y"), y)]);
+
+        assert!(equation.is_satisfied_by(&bindings(2.0, 1.0), 1e-9).unwrap());
+        assert!(!equation.is_satisfied_by(&bindings(1.0, 1.0), 1e-9).unwrap());
+        assert!(
+            equation
+                .is_satisfied_by(&bindings(2.0, 1.0 + 1e-12), 1e-9)
+                .unwrap()
+        );
+
+        let missing = HashMap::from([(String::from("x"), 2.0)]);
+        assert!(!equation.is_satisfied_by(&missing, 1e-9).unwrap());
+
+        let equation = equation_from_text("x / y = 1");
+        let error = equation
+            .is_satisfied_by(&bindings(1.0, 0.0), 1e-9)
+            .unwrap_err();
+        assert_eq!(error.side, Some(super::EquationSide::Left));
+    }
+
+    #[test]
+    fn test_error_expression() {
+        let message = |text: &str| {
+            super::Evaluator::new()
+                .evaluate_equation(&equation_from_text(text))
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            message("2 + x*y = 1"),
+            "Cannot multiply a variable times another variable. Column 5, in `x*y`, on the left-hand side"
+        );
+        assert!(message("1 = 3 + 4/(2 - 2)").contains(", in `4/(2 - 2)`, "));
+        assert!(message("\\sqrt(x + 1) = 2").contains(", in `\\sqrt(x + 1)`, "));
+
+        let error = super::Evaluator::new()
+            .with_max_depth(2)
+            .evaluate_expression(&equation_from_text("((1)) = 1").left)
+            .unwrap_err();
+        assert_eq!(error.expression, None);
+    }
 }
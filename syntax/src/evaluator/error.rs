@@ -1,22 +1,44 @@
+use crate::expression::Expression;
 use crate::tokenizer::Token;
 use std::fmt::{Display, Formatter};
 
+/// The operands of [`EvaluatorErrorType::VariableDivision`] and
+/// [`EvaluatorErrorType::VariableMultiplication`] are boxed, which keeps
+/// [`EvaluatorError`], returned by every evaluation step, small.
 #[derive(Debug)]
 pub enum EvaluatorErrorType {
     ZeroDivision,
     VariableDivision {
-        numerator: Token,
-        denominator: Token,
+        numerator: Box<Token>,
+        denominator: Box<Token>,
     },
     VariableMultiplication {
-        left: Token,
-        right: Token,
+        left: Box<Token>,
+        right: Box<Token>,
     },
     InvalidBinaryOperator,
     NonConstantExponent,
     NonConstantBase,
     ForbiddenParam,
     UndefinedFunction,
+    NumericOverflow,
+    RecursionLimit,
+    TermLimitExceeded,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EquationSide {
+    Left,
+    Right,
+}
+
+impl Display for EquationSide {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Left => f.write_str("left-hand side"),
+            Self::Right => f.write_str("right-hand side"),
+        }
+    }
 }
 
 pub type EvaluatorResult<T> = Result<T, EvaluatorError>;
@@ -25,6 +47,47 @@ pub type EvaluatorResult<T> = Result<T, EvaluatorError>;
 pub struct EvaluatorError {
     pub error_type: EvaluatorErrorType,
     pub token: Token,
+    pub side: Option<EquationSide>,
+    /// The innermost subexpression that failed, written in infix notation.
+    pub expression: Option<Box<str>>,
+}
+
+impl EvaluatorError {
+    /// A stable identifier for the kind of error, meant for branching on instead
+    /// of the message.
+    pub fn code(&self) -> &'static str {
+        use EvaluatorErrorType::*;
+        match self.error_type {
+            ZeroDivision => "zero_division",
+            VariableDivision { .. } => "variable_division",
+            VariableMultiplication { .. } => "variable_multiplication",
+            InvalidBinaryOperator => "invalid_binary_operator",
+            NonConstantExponent => "non_constant_exponent",
+            NonConstantBase => "non_constant_base",
+            ForbiddenParam => "forbidden_param",
+            UndefinedFunction => "undefined_function",
+            NumericOverflow => "numeric_overflow",
+            RecursionLimit => "recursion_limit",
+            TermLimitExceeded => "term_limit_exceeded",
+        }
+    }
+
+    /// Records `expression` as the subexpression that failed, unless a more
+    /// nested one already was.
+    pub fn in_expression(mut self, expression: &Expression) -> Self {
+        // The expression that hit the recursion limit is too deep to render.
+        if self.expression.is_none()
+            && !matches!(self.error_type, EvaluatorErrorType::RecursionLimit)
+        {
+            self.expression = Some(expression.to_infix().into());
+        }
+        self
+    }
+
+    pub fn on_side(mut self, side: EquationSide) -> Self {
+        self.side = Some(side);
+        self
+    }
 }
 
 impl Display for EvaluatorError {
@@ -71,6 +134,82 @@ impl Display for EvaluatorError {
                 "Function {} is undefined. Found in column {}",
                 self.token.lexeme, self.token.column
             ),
+            NumericOverflow => write!(
+                f,
+                "The result of the operation is too large or not a number. Column {}",
+                self.token.column
+            ),
+            RecursionLimit => write!(
+                f,
+                "The expression is nested too deeply to evaluate. Column {}",
+                self.token.column
+            ),
+            TermLimitExceeded => write!(
+                f,
+                "Expanding the product creates too many terms. Column {}",
+                self.token.column
+            ),
+        }?;
+
+        if let Some(expression) = &self.expression {
+            write!(f, ", in `{expression}`")?;
+        }
+
+        match self.side {
+            Some(side) => write!(f, ", on the {side}"),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::evaluator::{EvaluatorError, EvaluatorErrorType};
+    use crate::tokenizer::{Token, TokenType};
+
+    #[test]
+    fn test_codes() {
+        let token = Token::new(TokenType::Star, String::from("*"), 1);
+        let cases = [
+            (EvaluatorErrorType::ZeroDivision, "zero_division"),
+            (
+                EvaluatorErrorType::VariableDivision {
+                    numerator: Box::new(token.clone()),
+                    denominator: Box::new(token.clone()),
+                },
+                "variable_division",
+            ),
+            (
+                EvaluatorErrorType::VariableMultiplication {
+                    left: Box::new(token.clone()),
+                    right: Box::new(token.clone()),
+                },
+                "variable_multiplication",
+            ),
+            (
+                EvaluatorErrorType::InvalidBinaryOperator,
+                "invalid_binary_operator",
+            ),
+            (
+                EvaluatorErrorType::NonConstantExponent,
+                "non_constant_exponent",
+            ),
+            (EvaluatorErrorType::NonConstantBase, "non_constant_base"),
+            (EvaluatorErrorType::ForbiddenParam, "forbidden_param"),
+            (EvaluatorErrorType::UndefinedFunction, "undefined_function"),
+            (EvaluatorErrorType::NumericOverflow, "numeric_overflow"),
+            (EvaluatorErrorType::RecursionLimit, "recursion_limit"),
+            (EvaluatorErrorType::TermLimitExceeded, "term_limit_exceeded"),
+        ];
+
+        for (error_type, code) in cases {
+            let error = EvaluatorError {
+                error_type,
+                token: token.clone(),
+                side: None,
+                expression: None,
+            };
+            assert_eq!(error.code(), code);
         }
     }
 }
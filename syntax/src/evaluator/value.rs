@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 
 #[derive(Debug, PartialEq)]
@@ -24,13 +25,28 @@ impl Value {
         }
     }
 
+    /// The canonical zero, a constant monomial, which is what cancelling terms
+    /// evaluate to.
+    pub fn zero() -> Value {
+        Value::new_constant(0.0)
+    }
+
+    /// Whether every term cancels out once sums are flattened and like terms
+    /// combined.
+    pub fn is_zero(&self) -> bool {
+        self.combined_terms()
+            .values()
+            .all(|coefficient| *coefficient == 0.0)
+    }
+
     pub fn negate(self) -> Self {
         match self {
+            // Keeps zero from turning into `-0`.
             Self::Monomial {
                 coefficient,
                 variable,
             } => Self::Monomial {
-                coefficient: -coefficient,
+                coefficient: 0.0 - coefficient,
                 variable,
             },
             Self::Sum(values) => {
@@ -39,6 +55,58 @@ impl Value {
             }
         }
     }
+
+    /// Whether the value has no variables once its sums are flattened and like
+    /// terms combined, so `x + 2 - x` is constant.
+    pub fn is_constant(&self) -> bool {
+        self.as_constant().is_some()
+    }
+
+    /// The number the value adds up to, or `None` if any variable is left after
+    /// combining like terms.
+    pub fn as_constant(&self) -> Option<f64> {
+        let terms = self.combined_terms();
+        let has_variables = terms
+            .iter()
+            .any(|(variable, coefficient)| variable.is_some() && *coefficient != 0.0);
+
+        if has_variables {
+            return None;
+        }
+
+        Some(terms.get(&None).copied().unwrap_or(0.0))
+    }
+
+    /// Compares both values after flattening their sums and combining like terms,
+    /// so the order of the terms does not matter and coefficients may differ by up
+    /// to `epsilon`. A missing term counts as a zero coefficient.
+    pub fn approx_eq(&self, other: &Value, epsilon: f64) -> bool {
+        let left = self.combined_terms();
+        let right = other.combined_terms();
+
+        left.keys().chain(right.keys()).all(|variable| {
+            let a = left.get(variable).copied().unwrap_or(0.0);
+            let b = right.get(variable).copied().unwrap_or(0.0);
+            (a - b).abs() <= epsilon
+        })
+    }
+
+    fn combined_terms(&self) -> BTreeMap<Option<&str>, f64> {
+        let mut terms = BTreeMap::new();
+        let mut pending = vec![self];
+
+        while let Some(value) = pending.pop() {
+            match value {
+                Self::Sum(values) => pending.extend(values),
+                Self::Monomial {
+                    coefficient,
+                    variable,
+                } => *terms.entry(variable.as_deref()).or_default() += coefficient,
+            }
+        }
+
+        terms
+    }
 }
 
 impl Display for Value {
@@ -47,13 +115,13 @@ impl Display for Value {
             Self::Monomial {
                 coefficient,
                 variable,
-            } => {
-                let variable_fmt = match variable {
-                    Some(var) => var,
-                    None => "",
-                };
-                write!(f, "{coefficient}{variable_fmt}")
-            }
+            } => match variable {
+                // Unit coefficients are implied, so `1x` reads as `x`.
+                Some(var) if *coefficient == 1.0 => f.write_str(var),
+                Some(var) if *coefficient == -1.0 => write!(f, "-{var}"),
+                Some(var) => write!(f, "{coefficient}{var}"),
+                None => write!(f, "{coefficient}"),
+            },
             Self::Sum(values) => {
                 let mut iter = values.iter();
                 if let Some(first) = iter.next() {
@@ -68,3 +136,48 @@ impl Display for Value {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::evaluator::Value;
+
+    #[test]
+    fn test_as_constant() {
+        let constant = Value::Sum(vec![
+            Value::new_constant(2.0),
+            Value::Sum(vec![Value::new_constant(3.0)]),
+        ]);
+        assert!(constant.is_constant());
+        assert_eq!(constant.as_constant(), Some(5.0));
+
+        let cancelled = Value::Sum(vec![
+            Value::new_monomial(1.0, String::from("x")),
+            Value::new_constant(2.0),
+            Value::new_monomial(-1.0, String::from("x")),
+        ]);
+        assert_eq!(cancelled.as_constant(), Some(2.0));
+
+        let variable = Value::Sum(vec![
+            Value::new_constant(2.0),
+            Value::new_monomial(1.0, String::from("y")),
+        ]);
+        assert!(!variable.is_constant());
+        assert_eq!(variable.as_constant(), None);
+        assert_eq!(
+            Value::new_monomial(3.0, String::from("y")).as_constant(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_display_unit_coefficients() {
+        let value = Value::Sum(vec![
+            Value::new_monomial(1.0, String::from("x")),
+            Value::new_monomial(-1.0, String::from("y")),
+            Value::new_monomial(2.5, String::from("z")),
+            Value::new_constant(1.0),
+        ]);
+
+        assert_eq!(value.to_string(), "x + (-y) + (2.5z) + (1)");
+    }
+}
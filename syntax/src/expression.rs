@@ -1,5 +1,15 @@
-use crate::tokenizer::Token;
-use std::fmt::{Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use crate::evaluator::{Evaluator, Value};
+use crate::tokenizer::{Token, TokenType};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display, Formatter, Write};
+
+/// Binding strength of a negation for [`Expression::to_infix`]: it wraps a whole
+/// monomial, so it sits between products and powers.
+const NEGATION_RANK: u8 = 3;
 
 #[derive(Debug, PartialEq)]
 pub enum ExpressionType {
@@ -24,7 +34,186 @@ pub struct Expression {
     pub token: Token,
 }
 
-fn parenthesize(f: &mut Formatter<'_>, token: &str, exprs: &[&Expression]) -> std::fmt::Result {
+impl Expression {
+    /// Evaluates the expression to a number, returning `None` if it contains a
+    /// variable or cannot be evaluated.
+    #[cfg(feature = "std")]
+    pub fn evaluate_numeric(&self) -> Option<f64> {
+        match Evaluator::new().evaluate_expression(self).ok()? {
+            Value::Monomial {
+                coefficient,
+                variable: None,
+            } => Some(coefficient),
+            _ => None,
+        }
+    }
+
+    /// Iterates over this expression and every subexpression in pre-order, so a
+    /// binary node comes before its left operand, which comes before its right one.
+    pub fn descendants(&self) -> impl Iterator<Item = &Expression> {
+        // An explicit stack instead of recursion, since the tree can be deep.
+        let mut stack = vec![self];
+
+        core::iter::from_fn(move || {
+            let expression = stack.pop()?;
+            match &expression.expression_type {
+                ExpressionType::Binary { left, right, .. } => {
+                    stack.push(right);
+                    stack.push(left);
+                }
+                ExpressionType::Grouping(inner) | ExpressionType::Negation(inner) => {
+                    stack.push(inner)
+                }
+                ExpressionType::FunctionCall { parameter, .. } => stack.push(parameter),
+                ExpressionType::Number(_) | ExpressionType::Variable(_) => {}
+            }
+
+            Some(expression)
+        })
+    }
+
+    /// Whether the expression is linear in all of its variables. It is not when a
+    /// variable is raised to anything but `1`, multiplied by another variable,
+    /// divided by, or passed to a function.
+    pub fn is_linear(&self) -> bool {
+        self.descendants()
+            .all(|expression| match &expression.expression_type {
+                ExpressionType::Binary {
+                    left,
+                    operator,
+                    right,
+                } => match operator.token_type {
+                    TokenType::Star => !(left.has_variables() && right.has_variables()),
+                    TokenType::Slash => !right.has_variables(),
+                    TokenType::Hat => {
+                        !right.has_variables()
+                            && (!left.has_variables()
+                                || right.ungrouped().expression_type == ExpressionType::Number(1.0))
+                    }
+                    _ => true,
+                },
+                ExpressionType::FunctionCall { parameter, .. } => !parameter.has_variables(),
+                _ => true,
+            })
+    }
+
+    /// The operator of every binary node, in pre-order and with repetitions.
+    /// Implicit multiplications, like the one in `2x`, count as [`TokenType::Star`].
+    pub fn operators(&self) -> Vec<TokenType> {
+        self.descendants()
+            .filter_map(|expression| match &expression.expression_type {
+                ExpressionType::Binary { operator, .. } => Some(operator.token_type.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn has_variables(&self) -> bool {
+        self.descendants()
+            .any(|expression| matches!(expression.expression_type, ExpressionType::Variable(_)))
+    }
+
+    /// Renders the expression in the usual infix notation, such as `3*(x + 1)`,
+    /// only keeping the parentheses that the precedence of each operator needs.
+    /// [`Display`] keeps printing the S-expression form for debugging.
+    pub fn to_infix(&self) -> String {
+        let mut infix = String::new();
+        self.write_infix(&mut infix);
+        infix
+    }
+
+    fn write_infix(&self, out: &mut String) {
+        match &self.expression_type {
+            ExpressionType::Number(num) => {
+                let _ = write!(out, "{num}");
+            }
+            ExpressionType::Variable(name) => out.push_str(name),
+            ExpressionType::Grouping(inner) => inner.write_infix(out),
+            ExpressionType::Negation(inner) => {
+                out.push('-');
+                inner.write_operand(out, inner.infix_rank() <= NEGATION_RANK);
+            }
+            ExpressionType::FunctionCall { name, parameter } => {
+                let _ = write!(out, "\\{name}(");
+                parameter.write_infix(out);
+                out.push(')');
+            }
+            ExpressionType::Binary {
+                left,
+                operator,
+                right,
+            } => {
+                let rank = self.infix_rank();
+                let is_power = operator.token_type == TokenType::Hat;
+                left.write_operand(
+                    out,
+                    left.infix_rank() < rank || (is_power && left.infix_rank() == rank),
+                );
+
+                out.push_str(match operator.token_type {
+                    TokenType::Plus => " + ",
+                    TokenType::Minus => " - ",
+                    TokenType::Star => "*",
+                    TokenType::Slash => "/",
+                    _ => "^",
+                });
+
+                // `a + (b + c)` and `a*(b*c)` can drop their parentheses, but not
+                // `a - (b - c)`, `a/(b/c)` or `a^(b^c)`.
+                let right = right.ungrouped();
+                let associative = match &right.expression_type {
+                    ExpressionType::Binary {
+                        operator: inner, ..
+                    } => {
+                        inner.token_type == operator.token_type
+                            && matches!(operator.token_type, TokenType::Plus | TokenType::Star)
+                    }
+                    _ => false,
+                };
+                let right_rank = right.infix_rank();
+                right.write_operand(
+                    out,
+                    right_rank < rank
+                        || (right_rank == rank && !associative)
+                        || matches!(right.expression_type, ExpressionType::Negation(_)),
+                );
+            }
+        }
+    }
+
+    fn write_operand(&self, out: &mut String, parenthesize: bool) {
+        if parenthesize {
+            out.push('(');
+            self.write_infix(out);
+            out.push(')');
+        } else {
+            self.write_infix(out);
+        }
+    }
+
+    fn ungrouped(&self) -> &Expression {
+        match &self.expression_type {
+            ExpressionType::Grouping(inner) => inner.ungrouped(),
+            _ => self,
+        }
+    }
+
+    /// How tightly the expression binds when printed, higher binds tighter.
+    fn infix_rank(&self) -> u8 {
+        match &self.expression_type {
+            ExpressionType::Binary { operator, .. } => match operator.token_type {
+                TokenType::Plus | TokenType::Minus => 1,
+                TokenType::Star | TokenType::Slash => 2,
+                _ => 4,
+            },
+            ExpressionType::Negation(_) => NEGATION_RANK,
+            ExpressionType::Grouping(inner) => inner.infix_rank(),
+            _ => 5,
+        }
+    }
+}
+
+fn parenthesize(f: &mut Formatter<'_>, token: &str, exprs: &[&Expression]) -> core::fmt::Result {
     f.write_str("(")?;
     f.write_str(token)?;
     for expr in exprs {
@@ -34,20 +223,195 @@ fn parenthesize(f: &mut Formatter<'_>, token: &str, exprs: &[&Expression]) -> st
 }
 
 impl Display for Expression {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match &self.expression_type {
             ExpressionType::Binary {
                 left,
                 operator,
                 right,
-            } => parenthesize(f, &operator.lexeme, &[&left, &right]),
+            } => parenthesize(f, &operator.lexeme, &[left, right]),
             ExpressionType::Variable(varname) => f.write_str(varname),
-            ExpressionType::Grouping(expr) => parenthesize(f, "group", &[&expr]),
+            ExpressionType::Grouping(expr) => parenthesize(f, "group", &[expr]),
             ExpressionType::Number(num) => write!(f, "{num}"),
-            ExpressionType::Negation(expr) => parenthesize(f, "-", &[&expr]),
+            ExpressionType::Negation(expr) => parenthesize(f, "-", &[expr]),
             ExpressionType::FunctionCall { name, parameter } => {
                 write!(f, "(call {name} {parameter})")
             }
         }
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::expression::ExpressionType;
+    use crate::lexer::{Equation, Lexer};
+    use crate::tokenizer::{Token, TokenType, Tokenizer};
+    use std::io::Cursor;
+
+    #[cfg(test)]
+    fn equation_from_text(text: &str) -> Equation {
+        let tokenizer = Tokenizer::new(Cursor::new(text));
+        let tokens: Vec<Token> = tokenizer.map(|t| t.unwrap()).collect();
+
+        Lexer::new(tokens).equation().unwrap()
+    }
+
+    #[test]
+    fn test_evaluate_numeric() {
+        let equation = equation_from_text("2 + 3 * 4 = x + 1");
+
+        assert_eq!(equation.left.evaluate_numeric(), Some(14.0));
+        assert_eq!(equation.right.evaluate_numeric(), None);
+    }
+
+    #[test]
+    fn test_to_infix() {
+        let cases = [
+            ("x + 2", "x + 2"),
+            ("3(x + 1)", "3*(x + 1)"),
+            ("2x - ((y))", "2*x - y"),
+            ("-(x + 2) * 4", "-(x + 2)*4"),
+            ("(2 - 3) - x", "2 - 3 - x"),
+            ("2 - (3 - x)", "2 - (3 - x)"),
+            ("x + (y + z)", "x + y + z"),
+            ("a/(b*c) + (a + b)/c", "a/(b*c) + (a + b)/c"),
+            ("-2^2 + x*-y", "-2^2 + x*(-y)"),
+            ("\\sqrt(x + 1) * 2^(1 + 1)", "\\sqrt(x + 1)*2^(1 + 1)"),
+        ];
+
+        for (text, infix) in cases {
+            let equation = equation_from_text(&format!("{text} = 0"));
+            assert_eq!(equation.left.to_infix(), infix);
+        }
+    }
+
+    #[test]
+    fn test_descendants() {
+        let equation = equation_from_text("-(x + 2) = \\sin(3y) * 4");
+
+        let left: Vec<String> = equation
+            .left
+            .descendants()
+            .map(|expression| format!("{expression}"))
+            .collect();
+        assert_eq!(
+            left,
+            [
+                "(- (group (+ x 2)))",
+                "(group (+ x 2))",
+                "(+ x 2)",
+                "x",
+                "2"
+            ]
+        );
+
+        assert_eq!(equation.right.descendants().count(), 6);
+        let calls = equation
+            .right
+            .descendants()
+            .filter(|expression| {
+                matches!(
+                    expression.expression_type,
+                    ExpressionType::FunctionCall { .. }
+                )
+            })
+            .count();
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_operators() {
+        let equation = equation_from_text("2x + 3/y - z^2 = 0");
+        let operators = equation.left.operators();
+
+        assert_eq!(
+            operators,
+            [
+                TokenType::Minus,
+                TokenType::Plus,
+                TokenType::Star,
+                TokenType::Slash,
+                TokenType::Hat
+            ]
+        );
+        assert!(equation.right.operators().is_empty());
+    }
+
+    #[cfg(feature = "fuzz")]
+    #[test]
+    fn fuzz_infix_round_trip() {
+        use crate::simplifier::evaluate;
+
+        // xorshift64*, like the tokenizer fuzzer.
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        let mut next = |bound: u64| {
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            (state.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 32) % bound
+        };
+
+        // Writes a random expression, parenthesizing operands only half of the
+        // time so precedence is exercised as well.
+        fn generate(next: &mut impl FnMut(u64) -> u64, depth: u32, out: &mut String) {
+            let choice = if depth == 0 { next(2) } else { next(8) };
+            match choice {
+                0 => out.push_str(&(next(9) + 1).to_string()),
+                1 => out.push(['x', 'y', 'z'][next(3) as usize]),
+                2 => {
+                    out.push_str("-(");
+                    generate(next, depth - 1, out);
+                    out.push(')');
+                }
+                3 => {
+                    out.push('(');
+                    generate(next, depth - 1, out);
+                    out.push_str(")^");
+                    out.push_str(&next(4).to_string());
+                }
+                4 => {
+                    out.push_str("\\abs(");
+                    generate(next, depth - 1, out);
+                    out.push(')');
+                }
+                _ => {
+                    let operator = ["+", "-", "*", "/"][next(4) as usize];
+                    let grouped = next(2) == 0;
+
+                    for (index, operand) in [depth - 1, depth - 1].into_iter().enumerate() {
+                        if index == 1 {
+                            out.push_str(operator);
+                        }
+                        out.push_str(if grouped { "(" } else { "" });
+                        generate(next, operand, out);
+                        out.push_str(if grouped { ")" } else { "" });
+                    }
+                }
+            }
+        }
+
+        for _ in 0..5_000 {
+            let mut source = String::new();
+            generate(&mut next, 4, &mut source);
+
+            let tokens: Vec<Token> = match Tokenizer::new(Cursor::new(source.as_str())).collect() {
+                Ok(tokens) => tokens,
+                Err(_) => continue,
+            };
+            let Ok(expression) = Lexer::new(tokens).expression_only() else {
+                continue;
+            };
+            let Ok(expected) = evaluate(&source) else {
+                continue;
+            };
+
+            let infix = expression.to_infix();
+            let actual = evaluate(&infix)
+                .unwrap_or_else(|e| panic!("{source} printed as {infix} fails with {e}"));
+            assert!(
+                expected.approx_eq(&actual, 1e-9),
+                "{source} printed as {infix} evaluates to {actual} instead of {expected}"
+            );
+        }
+    }
+}
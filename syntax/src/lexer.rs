@@ -3,17 +3,112 @@ mod error;
 use crate::expression::{Expression, ExpressionType};
 use crate::lexer::error::LexerResult;
 use crate::tokenizer::{Token, TokenType};
+use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
 
 pub use crate::lexer::error::LexerError;
 
+/// How deeply monomials may nest before parsing gives up, so hostile input such
+/// as `((((...))))` cannot overflow the stack. Every level takes several frames,
+/// so this stays well below what a 2 MiB thread stack can hold in debug builds.
+const DEFAULT_MAX_DEPTH: usize = 64;
+
+/// The evaluator's built-in functions, which can also be called without a
+/// backslash when `(` follows right away, as in `sin(x)`. Without the `(`, as in
+/// `sin` or `sinx`, the name is still a variable.
+pub(crate) const BUILTIN_FUNCTIONS: [&str; 11] = [
+    "ln", "log", "log2", "abs", "sin", "tan", "cos", "exp", "sqrt", "floor", "ceil",
+];
+
 pub struct Lexer {
     tokens: Vec<Token>,
     current: usize,
+    depth: usize,
+    max_depth: usize,
+    open_bars: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relation {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+}
+
+impl Relation {
+    fn from_token_type(token_type: &TokenType) -> Option<Self> {
+        match token_type {
+            TokenType::Less => Some(Self::Lt),
+            TokenType::LessEqual => Some(Self::Le),
+            TokenType::Greater => Some(Self::Gt),
+            TokenType::GreaterEqual => Some(Self::Ge),
+            TokenType::Equal | TokenType::ColonEqual => Some(Self::Eq),
+            _ => None,
+        }
+    }
 }
 
+impl Display for Relation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Lt => f.write_str("<"),
+            Self::Le => f.write_str("<="),
+            Self::Gt => f.write_str(">"),
+            Self::Ge => f.write_str(">="),
+            Self::Eq => f.write_str("="),
+        }
+    }
+}
+
+/// Build one with [`Equation::new`]; it is non-exhaustive so that new fields
+/// can be added without breaking downstream code.
+#[non_exhaustive]
 pub struct Equation {
     pub left: Expression,
     pub right: Expression,
+    pub relation: Relation,
+    /// Whether the equation was written with `:=`, as a definition rather than a
+    /// constraint. It is otherwise read just like `=`.
+    pub assignment: bool,
+}
+
+impl Equation {
+    pub fn new(left: Expression, right: Expression, relation: Relation) -> Self {
+        Self {
+            left,
+            right,
+            relation,
+            assignment: false,
+        }
+    }
+
+    /// Marks the equation as written with `:=` instead of `=`.
+    pub fn with_assignment(mut self, assignment: bool) -> Self {
+        self.assignment = assignment;
+        self
+    }
+
+    /// Whether both sides are linear, see [`Expression::is_linear`].
+    pub fn is_linear(&self) -> bool {
+        self.left.is_linear() && self.right.is_linear()
+    }
+
+    /// Every distinct variable on either side, sorted by name.
+    pub fn variables(&self) -> BTreeSet<&str> {
+        self.left
+            .descendants()
+            .chain(self.right.descendants())
+            .filter_map(|expression| match &expression.expression_type {
+                ExpressionType::Variable(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 macro_rules! match_token {
@@ -55,23 +150,84 @@ macro_rules! expect_token {
 
 impl Lexer {
     pub fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, current: 0 }
+        Self {
+            tokens,
+            current: 0,
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+            open_bars: 0,
+        }
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// The tokens not consumed yet. After an [`LexerError::ExpectedEof`] this
+    /// starts with the token that could not be parsed.
+    pub fn remaining(&self) -> &[Token] {
+        self.tokens.get(self.current..).unwrap_or_default()
     }
 
     pub fn equation(&mut self) -> LexerResult<Equation> {
         let left = self.expression()?;
 
-        expect_token!(self, TokenType::Equal, Equal);
+        self.reject_stray_paren()?;
+        let assignment = match_token!(self, TokenType::ColonEqual);
+        if !assignment {
+            expect_token!(self, TokenType::Equal, Equal);
+        }
 
         let right = self.expression()?;
+        self.expect_eof()?;
 
-        if self.current < self.tokens.len() {
-            return Err(LexerError::ExpectedEof {
-                found: self.peek().unwrap().token_type.clone(),
-            });
-        }
+        Ok(Equation::new(left, right, Relation::Eq).with_assignment(assignment))
+    }
+
+    /// Parses an equation or an inequality, accepting any of `=`, `<`, `<=`, `>`
+    /// and `>=` between both sides.
+    pub fn relation(&mut self) -> LexerResult<Equation> {
+        let left = self.expression()?;
+
+        self.reject_stray_paren()?;
+        let relation = match self.peek() {
+            Some(token) => match Relation::from_token_type(&token.token_type) {
+                Some(relation) => relation,
+                None => {
+                    return Err(LexerError::ExpectedRelation {
+                        found: token.token_type.clone(),
+                    });
+                }
+            },
+            None => return Err(LexerError::UnexpectedEof),
+        };
+        let assignment = self
+            .advance()
+            .is_some_and(|token| token.token_type == TokenType::ColonEqual);
+
+        let right = self.expression()?;
+        self.expect_eof()?;
+
+        Ok(Equation::new(left, right, relation).with_assignment(assignment))
+    }
+
+    /// Parses a lone expression, with no relation, that must span the whole input.
+    pub fn expression_only(&mut self) -> LexerResult<Expression> {
+        let expression = self.expression()?;
+        self.expect_eof()?;
 
-        Ok(Equation { left, right })
+        Ok(expression)
+    }
+
+    fn expect_eof(&self) -> LexerResult<()> {
+        self.reject_stray_paren()?;
+        match self.peek() {
+            Some(token) => Err(LexerError::ExpectedEof {
+                found: token.token_type.clone(),
+            }),
+            None => Ok(()),
+        }
     }
 
     fn expression(&mut self) -> LexerResult<Expression> {
@@ -121,7 +277,22 @@ impl Lexer {
         Ok(factor)
     }
 
+    /// Every recursive rule goes through a monomial, so this is the only place
+    /// where the nesting depth is tracked.
     fn monomial(&mut self) -> LexerResult<Expression> {
+        if self.depth >= self.max_depth {
+            return Err(LexerError::RecursionLimit);
+        }
+
+        self.depth += 1;
+        let monomial = self.parse_monomial();
+        self.depth -= 1;
+        monomial
+    }
+
+    fn parse_monomial(&mut self) -> LexerResult<Expression> {
+        // The negation wraps the whole monomial, exponent included, so unary minus
+        // binds looser than `^` and `-2^2` means `-(2^2)`.
         if match_token!(self, TokenType::Minus) {
             let right = self.monomial()?;
             return Ok(Expression {
@@ -130,24 +301,13 @@ impl Lexer {
             });
         }
 
-        if match_token!(self, TokenType::FunctionName(_)) {
-            let previous = self.previous().unwrap().clone();
-            let name = &previous.lexeme[1..];
-
-            expect_token!(self, TokenType::LeftParen, LeftParen);
-            let parameter = self.expression()?;
-            expect_token!(self, TokenType::RightParen, RightParen);
-
-            return Ok(Expression {
-                expression_type: ExpressionType::FunctionCall {
-                    name: String::from(name),
-                    parameter: Box::new(parameter),
-                },
-                token: previous,
-            });
-        }
-
-        let mut primary = self.primary()?;
+        // A call takes exponents and implicit products like any other primary,
+        // as in `\sin(x)^2`.
+        let mut primary = if match_token!(self, TokenType::FunctionName(_)) {
+            self.parse_function_call()?
+        } else {
+            self.primary()?
+        };
 
         let next = match self.peek() {
             Some(next) => next.clone(),
@@ -155,38 +315,13 @@ impl Lexer {
         };
 
         match &next.token_type {
-            TokenType::Identifier(_) => {
-                let right = self.monomial()?;
-                primary = Expression {
-                    expression_type: ExpressionType::Binary {
-                        left: Box::new(primary),
-                        right: Box::new(right),
-                        operator: Token::new(TokenType::Star, String::from("*"), next.column),
-                    },
-                    token: next.clone(),
-                }
+            TokenType::Identifier(_) | TokenType::LeftParen | TokenType::FunctionName(_) => {
+                primary = self.implicit_multiplication(primary)?;
             }
-            TokenType::LeftParen => {
-                let right = self.monomial()?;
-                primary = Expression {
-                    expression_type: ExpressionType::Binary {
-                        left: Box::new(primary),
-                        operator: Token::new(TokenType::Star, String::from("*"), next.column),
-                        right: Box::new(right),
-                    },
-                    token: next.clone(),
-                }
-            }
-            TokenType::FunctionName(_) => {
-                let right = self.monomial()?;
-                primary = Expression {
-                    expression_type: ExpressionType::Binary {
-                        left: Box::new(primary),
-                        operator: Token::new(TokenType::Star, String::from("*"), next.column),
-                        right: Box::new(right),
-                    },
-                    token: next.clone(),
-                }
+            // Inside `|...|` a bar after an operand always closes, so `|2|x||`
+            // cannot be read as `|2 * |x||`.
+            TokenType::Bar if self.open_bars == 0 => {
+                primary = self.implicit_multiplication(primary)?;
             }
             TokenType::Hat => {
                 self.advance();
@@ -201,6 +336,27 @@ impl Lexer {
                     token: next.clone(),
                 }
             }
+            // `x3` is rejected rather than read as `3x`: a coefficient always goes
+            // before its variable.
+            TokenType::Number(_)
+                if matches!(primary.expression_type, ExpressionType::Variable(_)) =>
+            {
+                return Err(LexerError::NumberAfterIdentifier {
+                    identifier: primary.token.lexeme,
+                    number: next.lexeme,
+                });
+            }
+            // Likewise `(x + 1)2` is rejected, keeping numbers in front.
+            TokenType::Number(_)
+                if matches!(
+                    primary.expression_type,
+                    ExpressionType::Grouping(_) | ExpressionType::FunctionCall { .. }
+                ) =>
+            {
+                return Err(LexerError::NumberAfterGroup {
+                    number: next.lexeme,
+                });
+            }
             _ => {}
         }
 
@@ -225,6 +381,21 @@ impl Lexer {
                 self.advance();
                 self.parse_group(token)
             }
+            TokenType::Bar => {
+                self.advance();
+                self.parse_absolute_value(token)
+            }
+            TokenType::Identifier(name)
+                if BUILTIN_FUNCTIONS.contains(&name.as_str())
+                    && self
+                        .tokens
+                        .get(self.current + 1)
+                        .is_some_and(|next| next.token_type == TokenType::LeftParen) =>
+            {
+                self.advance();
+                self.advance();
+                self.parse_call_argument(name.clone(), token)
+            }
             TokenType::Identifier(varname) => {
                 self.advance();
                 Ok(Expression {
@@ -232,12 +403,68 @@ impl Lexer {
                     token,
                 })
             }
+            _ if token.is_operator() => Err(LexerError::UnexpectedOperator {
+                operator: token.lexeme,
+                line: token.line,
+                column: token.column,
+            }),
             other => Err(LexerError::ExpectedPrimary {
                 found: other.clone(),
             }),
         }
     }
 
+    /// Multiplies `left` by the monomial that follows it. The `*` is synthesized
+    /// right after the last token of `left`, where it would have been written,
+    /// so errors about the product point between both operands.
+    fn implicit_multiplication(&mut self, left: Expression) -> LexerResult<Expression> {
+        let (line, column) = self
+            .previous()
+            .map_or((left.token.line, left.token.column), |last| {
+                (last.line, last.column + last.lexeme.len())
+            });
+        let operator = Token::new(TokenType::Star, String::from("*"), column).with_line(line);
+        let right = self.monomial()?;
+
+        Ok(Expression {
+            expression_type: ExpressionType::Binary {
+                left: Box::new(left),
+                operator: operator.clone(),
+                right: Box::new(right),
+            },
+            token: operator,
+        })
+    }
+
+    fn parse_function_call(&mut self) -> LexerResult<Expression> {
+        let previous = self.previous().unwrap().clone();
+        let name = String::from(&previous.lexeme[1..]);
+
+        // Only a backslash makes a function, and it must be applied right away:
+        // `\sin x` is rejected instead of being read as `\sin(x)`.
+        if name.is_empty() || !match_token!(self, TokenType::LeftParen) {
+            return Err(LexerError::InvalidFunctionCall { name });
+        }
+
+        self.parse_call_argument(name, previous)
+    }
+
+    /// Parses the argument of a call to `name` up to its `)`, once the `(` has
+    /// been consumed.
+    fn parse_call_argument(&mut self, name: String, token: Token) -> LexerResult<Expression> {
+        let open = self.previous().unwrap().clone();
+        let parameter = self.expression()?;
+        self.close_paren(&open)?;
+
+        Ok(Expression {
+            expression_type: ExpressionType::FunctionCall {
+                name,
+                parameter: Box::new(parameter),
+            },
+            token,
+        })
+    }
+
     fn parse_exponent(&mut self) -> LexerResult<Expression> {
         let next = match self.peek() {
             Some(next) => next.clone(),
@@ -251,7 +478,7 @@ impl Lexer {
 
     fn parse_group(&mut self, token: Token) -> LexerResult<Expression> {
         let group = self.expression()?;
-        expect_token!(self, TokenType::RightParen, RightParen);
+        self.close_paren(&token)?;
 
         Ok(Expression {
             expression_type: ExpressionType::Grouping(Box::new(group)),
@@ -259,6 +486,48 @@ impl Lexer {
         })
     }
 
+    /// Consumes the `)` matching `open`, which must come next.
+    fn close_paren(&mut self, open: &Token) -> LexerResult<()> {
+        if match_token!(self, TokenType::RightParen) {
+            Ok(())
+        } else {
+            Err(LexerError::UnmatchedParen {
+                open_line: open.line,
+                open_column: open.column,
+            })
+        }
+    }
+
+    /// A `)` where an expression has already ended was never opened.
+    fn reject_stray_paren(&self) -> LexerResult<()> {
+        match self.peek() {
+            Some(token) if token.token_type == TokenType::RightParen => {
+                Err(LexerError::UnmatchedParen {
+                    open_line: token.line,
+                    open_column: token.column,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Parses the inside of `|...|` as a call to `abs`.
+    fn parse_absolute_value(&mut self, token: Token) -> LexerResult<Expression> {
+        self.open_bars += 1;
+        let inner = self.expression();
+        self.open_bars -= 1;
+        let inner = inner?;
+        expect_token!(self, TokenType::Bar, Bar);
+
+        Ok(Expression {
+            expression_type: ExpressionType::FunctionCall {
+                name: String::from("abs"),
+                parameter: Box::new(inner),
+            },
+            token,
+        })
+    }
+
     fn peek(&self) -> Option<&Token> {
         self.tokens.get(self.current)
     }
@@ -282,11 +551,11 @@ impl Lexer {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use crate::expression::ExpressionType;
-    use crate::lexer::Lexer;
-    use crate::tokenizer::{Token, Tokenizer};
+    use crate::lexer::{Equation, Lexer, LexerError, Relation};
+    use crate::tokenizer::{Token, TokenType, Tokenizer};
     use std::io::{BufReader, Cursor};
 
     #[cfg(test)]
@@ -541,4 +810,343 @@ mod tests {
         assert_eq!(left, "(call cos (+ 1 (* 2 x)))");
         assert_eq!(right, "(call tan (+ (+ (* 3 x) (* 10 y)) 2))")
     }
+
+    #[test]
+    fn test_builtin_names_without_backslash() {
+        let tokens = text_into_tokens("sinx = sin(x) + sin");
+        let mut lexer = Lexer::new(tokens);
+        let equation = lexer.equation().unwrap();
+
+        assert_eq!(format!("{}", equation.left), "sinx");
+        assert_eq!(format!("{}", equation.right), "(+ (call sin x) sin)");
+
+        let mut lexer = Lexer::new(text_into_tokens("2sqrt(x)^2 = sin x"));
+        let equation = lexer.equation().unwrap();
+        assert_eq!(format!("{}", equation.left), "(* 2 (^ (call sqrt x) 2))");
+        assert_eq!(format!("{}", equation.right), "(* sin x)");
+
+        // Only the evaluator's functions are calls; any other name is a variable.
+        let mut lexer = Lexer::new(text_into_tokens("f(x) = 1"));
+        let equation = lexer.equation().unwrap();
+        assert_eq!(format!("{}", equation.left), "(* f (group x))");
+    }
+
+    #[test]
+    fn test_invalid_function_calls() {
+        for text in ["\\sin x = 0", "\\sin = 0", "\\(x) = 0"] {
+            let mut lexer = Lexer::new(text_into_tokens(text));
+
+            assert!(matches!(
+                lexer.equation(),
+                Err(LexerError::InvalidFunctionCall { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_recursion_limit() {
+        let nested = format!("{}x{} = 1", "(".repeat(60), ")".repeat(60));
+        assert!(Lexer::new(text_into_tokens(&nested)).equation().is_ok());
+
+        let mut lexer = Lexer::new(text_into_tokens(&nested)).with_max_depth(50);
+        assert!(matches!(lexer.equation(), Err(LexerError::RecursionLimit)));
+
+        let deep = format!("{}x = 1", "-".repeat(10_000));
+        let mut lexer = Lexer::new(text_into_tokens(&deep));
+        assert!(matches!(lexer.equation(), Err(LexerError::RecursionLimit)));
+    }
+
+    #[test]
+    fn test_number_after_identifier() {
+        let mut lexer = Lexer::new(text_into_tokens("x 3 + 2 = 1"));
+
+        match lexer.equation() {
+            Err(LexerError::NumberAfterIdentifier { identifier, number }) => {
+                assert_eq!(identifier, "x");
+                assert_eq!(number, "3");
+            }
+            _ => panic!("Expected a number after identifier error"),
+        }
+
+        let mut lexer = Lexer::new(text_into_tokens("3x + x^2 = 1"));
+        assert!(lexer.equation().is_ok());
+
+        // Digits after the first letter are part of the name.
+        let mut lexer = Lexer::new(text_into_tokens("x3 + 2 = 1"));
+        let equation = lexer.equation().unwrap();
+        assert_eq!(format!("{}", equation.left), "(+ x3 2)");
+    }
+
+    #[test]
+    fn test_equation_rejects_trailing_comma() {
+        let mut lexer = Lexer::new(text_into_tokens("x = 1, y = 2"));
+
+        assert!(matches!(
+            lexer.equation(),
+            Err(LexerError::ExpectedEof {
+                found: TokenType::Comma
+            })
+        ));
+    }
+
+    #[test]
+    fn test_remaining() {
+        let mut lexer = Lexer::new(text_into_tokens("x = 1, y = 2"));
+        assert_eq!(lexer.remaining().len(), 7);
+
+        assert!(lexer.equation().is_err());
+        let remaining: Vec<&TokenType> = lexer
+            .remaining()
+            .iter()
+            .map(|token| &token.token_type)
+            .collect();
+        assert_eq!(
+            remaining,
+            [
+                &TokenType::Comma,
+                &TokenType::Identifier(String::from("y")),
+                &TokenType::Equal,
+                &TokenType::Number(2.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_linear() {
+        let linear = [
+            "2x + 3y = 4",
+            "x^1 + 2^3 = (x + 1)/2",
+            "3(x - y) = \\sqrt(4)",
+        ];
+        for text in linear {
+            assert!(
+                Lexer::new(text_into_tokens(text))
+                    .equation()
+                    .unwrap()
+                    .is_linear()
+            );
+        }
+
+        let nonlinear = [
+            "x^2 = 1",
+            "x*y = 1",
+            "2 = 3(x + 1)y",
+            "1/x = 2",
+            "2^x = 1",
+            "\\sin(x) = 0",
+        ];
+        for text in nonlinear {
+            assert!(
+                !Lexer::new(text_into_tokens(text))
+                    .equation()
+                    .unwrap()
+                    .is_linear()
+            );
+        }
+    }
+
+    #[test]
+    fn test_relations() {
+        let tokens = text_into_tokens("2x + 1 < 3");
+        let mut lexer = Lexer::new(tokens);
+        let equation = lexer.relation().unwrap();
+
+        assert_eq!(format!("{}", equation.left), "(+ (* 2 x) 1)");
+        assert_eq!(format!("{}", equation.right), "3");
+        assert_eq!(equation.relation, Relation::Lt);
+
+        let tokens = text_into_tokens("x >= y");
+        let mut lexer = Lexer::new(tokens);
+        assert_eq!(lexer.relation().unwrap().relation, Relation::Ge);
+
+        let tokens = text_into_tokens("x = y");
+        let mut lexer = Lexer::new(tokens);
+        assert_eq!(lexer.relation().unwrap().relation, Relation::Eq);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_equation_rejects_inequality() {
+        let tokens = text_into_tokens("x <= y");
+        let mut lexer = Lexer::new(tokens);
+        lexer.equation().unwrap();
+    }
+
+    #[test]
+    fn test_negation_binds_looser_than_exponent() {
+        let tokens = text_into_tokens("-2^2 = -x^3");
+        let mut lexer = Lexer::new(tokens);
+        let equation = lexer.equation().unwrap();
+
+        assert_eq!(format!("{}", equation.left), "(- (^ 2 2))");
+        assert_eq!(format!("{}", equation.right), "(- (^ x 3))");
+    }
+
+    #[test]
+    fn test_absolute_value_bars() {
+        let tokens = text_into_tokens("|x - 3| = 2|y|");
+        let mut lexer = Lexer::new(tokens);
+        let equation = lexer.equation().unwrap();
+
+        assert_eq!(format!("{}", equation.left), "(call abs (- x 3))");
+        assert_eq!(format!("{}", equation.right), "(* 2 (call abs y))");
+
+        let tokens = text_into_tokens("||x| - 1| = 0");
+        let mut lexer = Lexer::new(tokens);
+        let equation = lexer.equation().unwrap();
+        assert_eq!(
+            format!("{}", equation.left),
+            "(call abs (- (call abs x) 1))"
+        );
+
+        let tokens = text_into_tokens("|x - 3 = 2");
+        let mut lexer = Lexer::new(tokens);
+        assert!(matches!(
+            lexer.equation(),
+            Err(LexerError::WrongToken {
+                expected: TokenType::Bar,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_unmatched_parens() {
+        let tokens = text_into_tokens("2(x + (1 = 3");
+        let mut lexer = Lexer::new(tokens);
+        assert!(matches!(
+            lexer.equation(),
+            Err(LexerError::UnmatchedParen {
+                open_line: 1,
+                open_column: 7
+            })
+        ));
+
+        let tokens = text_into_tokens("x + 1) = 3");
+        let mut lexer = Lexer::new(tokens);
+        assert!(matches!(
+            lexer.equation(),
+            Err(LexerError::UnmatchedParen {
+                open_line: 1,
+                open_column: 6
+            })
+        ));
+
+        let tokens = text_into_tokens("x = (3))");
+        let mut lexer = Lexer::new(tokens);
+        assert!(matches!(
+            lexer.equation(),
+            Err(LexerError::UnmatchedParen {
+                open_line: 1,
+                open_column: 8
+            })
+        ));
+
+        let tokens = text_into_tokens("\\sqrt(x");
+        let mut lexer = Lexer::new(tokens);
+        assert!(matches!(
+            lexer.expression_only(),
+            Err(LexerError::UnmatchedParen {
+                open_line: 1,
+                open_column: 6
+            })
+        ));
+    }
+
+    #[test]
+    fn test_unexpected_operator() {
+        let tokens = text_into_tokens("x + * 3 = 1");
+        let mut lexer = Lexer::new(tokens);
+        let Err(error) = lexer.equation() else {
+            panic!("Expected an error");
+        };
+
+        assert!(matches!(
+            &error,
+            LexerError::UnexpectedOperator { operator, line: 1, column: 5 } if operator == "*"
+        ));
+        assert_eq!(
+            error.to_string(),
+            "Expected a value but found the operator '*' in line 1, column 5"
+        );
+    }
+
+    #[test]
+    fn test_equation_variables() {
+        let tokens = text_into_tokens("2y + x(3 + z) = x - \\sqrt(4)");
+        let mut lexer = Lexer::new(tokens);
+        let equation = lexer.equation().unwrap();
+
+        assert_eq!(
+            equation.variables().into_iter().collect::<Vec<_>>(),
+            ["x", "y", "z"]
+        );
+    }
+
+    #[test]
+    fn test_number_after_group() {
+        let mut lexer = Lexer::new(text_into_tokens("(x + 1)2 = 4"));
+        assert!(matches!(
+            lexer.equation(),
+            Err(LexerError::NumberAfterGroup { number }) if number == "2"
+        ));
+
+        let message = |text: &str| match Lexer::new(text_into_tokens(text)).equation() {
+            Err(error) => error.to_string(),
+            Ok(_) => panic!("{text} should not parse"),
+        };
+        assert_eq!(
+            message("x = |y|3"),
+            "Found 3 after a group, coefficients must be written first, as in 3(x + 1)"
+        );
+        assert_eq!(
+            message("\\abs(2)3 = x"),
+            "Found 3 after a group, coefficients must be written first, as in 3(x + 1)"
+        );
+
+        let mut lexer = Lexer::new(text_into_tokens("2(x + 1) = (x + 1)*2"));
+        assert!(lexer.equation().is_ok());
+    }
+
+    #[test]
+    fn test_assignment() {
+        let mut lexer = Lexer::new(text_into_tokens("y := x + 1"));
+        let equation = lexer.equation().unwrap();
+
+        assert!(equation.assignment);
+        assert_eq!(equation.relation, Relation::Eq);
+        assert_eq!(format!("{}", equation.left), "y");
+        assert_eq!(format!("{}", equation.right), "(+ x 1)");
+
+        let mut lexer = Lexer::new(text_into_tokens("y := x + 1"));
+        assert!(lexer.relation().unwrap().assignment);
+
+        let mut lexer = Lexer::new(text_into_tokens("y = x + 1"));
+        assert!(!lexer.equation().unwrap().assignment);
+
+        let parsed = Lexer::new(text_into_tokens("y = 2")).equation().unwrap();
+        let equation = Equation::new(parsed.left, parsed.right, Relation::Ge);
+        assert!(!equation.assignment);
+        assert_eq!(equation.relation, Relation::Ge);
+        assert!(equation.with_assignment(true).assignment);
+    }
+
+    #[test]
+    fn test_function_call_operands() {
+        let mut lexer = Lexer::new(text_into_tokens("\\sqrt(4)^3 = \\abs(2)x"));
+        let equation = lexer.equation().unwrap();
+
+        assert_eq!(format!("{}", equation.left), "(^ (call sqrt 4) 3)");
+        assert_eq!(format!("{}", equation.right), "(* (call abs 2) x)");
+    }
+
+    #[test]
+    fn test_implicit_multiplication_column() {
+        let mut lexer = Lexer::new(text_into_tokens("2 x = (1)(y)"));
+        let equation = lexer.equation().unwrap();
+
+        assert_eq!(equation.left.token.column, 2);
+        assert_eq!(equation.left.token.token_type, TokenType::Star);
+        assert_eq!(equation.right.token.column, 10);
+    }
 }
@@ -1,6 +1,7 @@
 use crate::tokenizer::{TokenType, TokenizerError};
-use std::error::Error;
-use std::fmt::{Display, Formatter};
+use alloc::string::String;
+use core::error::Error;
+use core::fmt::{Display, Formatter};
 
 pub type LexerResult<T> = Result<T, LexerError>;
 
@@ -24,10 +25,86 @@ pub enum LexerError {
     InvalidExponent {
         found: TokenType,
     },
+    ExpectedRelation {
+        found: TokenType,
+    },
+    /// A `\` that is not followed by a name and a left parenthesis. An empty
+    /// name means the backslash stood on its own.
+    InvalidFunctionCall {
+        name: String,
+    },
+    RecursionLimit,
+    /// A `(` that is never closed, or a `)` that closes nothing, at the given
+    /// line and column.
+    UnmatchedParen {
+        open_line: usize,
+        open_column: usize,
+    },
+    /// An operator where a value was expected, like the `*` in `x + * 3`.
+    UnexpectedOperator {
+        operator: String,
+        line: usize,
+        column: usize,
+    },
+    /// A number written right after a variable, like `x3`.
+    NumberAfterIdentifier {
+        identifier: String,
+        number: String,
+    },
+    /// A number written right after a parenthesized group or a call, like
+    /// `(x + 1)2`.
+    NumberAfterGroup {
+        number: String,
+    },
+}
+
+impl LexerError {
+    /// A stable identifier for the kind of error, meant for branching on instead
+    /// of the message. Tokenizer errors keep their own code.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::TokenizerError(e) => e.code(),
+            Self::WrongToken { .. } => "wrong_token",
+            Self::ExpectedTokenFoundEof { .. } => "expected_token_found_eof",
+            Self::UnexpectedEof => "unexpected_eof",
+            Self::ExpectedEof { .. } => "expected_eof",
+            Self::ExpectedPrimary { .. } => "expected_primary",
+            Self::InvalidExponent { .. } => "invalid_exponent",
+            Self::ExpectedRelation { .. } => "expected_relation",
+            Self::InvalidFunctionCall { .. } => "invalid_function_call",
+            Self::RecursionLimit => "recursion_limit",
+            Self::UnmatchedParen { .. } => "unmatched_paren",
+            Self::UnexpectedOperator { .. } => "unexpected_operator",
+            Self::NumberAfterIdentifier { .. } => "number_after_identifier",
+            Self::NumberAfterGroup { .. } => "number_after_group",
+        }
+    }
+}
+
+impl LexerError {
+    /// The line where the error was found, for the errors that know it.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            Self::TokenizerError(e) => Some(e.line()),
+            Self::UnmatchedParen { open_line, .. } => Some(*open_line),
+            Self::UnexpectedOperator { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    /// The column where the error was found, for the errors that know it.
+    pub fn column(&self) -> Option<usize> {
+        match self {
+            Self::TokenizerError(e) => Some(e.column()),
+            Self::UnmatchedParen { open_column, .. } => Some(*open_column),
+            Self::UnexpectedOperator { column, .. } => Some(*column),
+            _ => None,
+        }
+    }
 }
 
 impl Display for LexerError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::TokenizerError(e) => write!(f, "Syntax Error: {e}"),
             Self::WrongToken { found, expected } => {
@@ -50,8 +127,136 @@ impl Display for LexerError {
                 f,
                 "Expected identifier, number or group for exponent, but found {found:?} instead"
             ),
+            Self::ExpectedRelation { found } => write!(
+                f,
+                "Expected one of =, <, <=, > or >=, but found {found:?} instead"
+            ),
+            Self::InvalidFunctionCall { name } if name.is_empty() => {
+                f.write_str("Expected a function name after \\")
+            }
+            Self::NumberAfterIdentifier { identifier, number } => write!(
+                f,
+                "Found {number} after variable {identifier}, coefficients must be written first, as in {number}{identifier}"
+            ),
+            Self::NumberAfterGroup { number } => write!(
+                f,
+                "Found {number} after a group, coefficients must be written first, as in {number}(x + 1)"
+            ),
+            Self::RecursionLimit => f.write_str("The expression is nested too deeply"),
+            Self::UnexpectedOperator {
+                operator,
+                line,
+                column,
+            } => write!(
+                f,
+                "Expected a value but found the operator '{operator}' in line {line}, column {column}"
+            ),
+            Self::UnmatchedParen {
+                open_line,
+                open_column,
+            } => write!(
+                f,
+                "Unmatched parenthesis in line {open_line}, column {open_column}"
+            ),
+            Self::InvalidFunctionCall { name } => write!(
+                f,
+                "Function \\{name} must be followed by a parenthesized argument"
+            ),
         }
     }
 }
 
 impl Error for LexerError {}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::lexer::LexerError;
+    use crate::tokenizer::{TokenType, TokenizerError};
+
+    #[test]
+    fn test_codes() {
+        let cases = [
+            (
+                LexerError::TokenizerError(TokenizerError::MalformedNumber(1, 1)),
+                "malformed_number",
+            ),
+            (
+                LexerError::WrongToken {
+                    found: TokenType::Plus,
+                    expected: TokenType::Equal,
+                },
+                "wrong_token",
+            ),
+            (
+                LexerError::ExpectedTokenFoundEof {
+                    expected: TokenType::Equal,
+                },
+                "expected_token_found_eof",
+            ),
+            (LexerError::UnexpectedEof, "unexpected_eof"),
+            (
+                LexerError::ExpectedEof {
+                    found: TokenType::Plus,
+                },
+                "expected_eof",
+            ),
+            (
+                LexerError::ExpectedPrimary {
+                    found: TokenType::Equal,
+                },
+                "expected_primary",
+            ),
+            (
+                LexerError::InvalidExponent {
+                    found: TokenType::Equal,
+                },
+                "invalid_exponent",
+            ),
+            (
+                LexerError::ExpectedRelation {
+                    found: TokenType::Plus,
+                },
+                "expected_relation",
+            ),
+            (
+                LexerError::InvalidFunctionCall {
+                    name: String::new(),
+                },
+                "invalid_function_call",
+            ),
+            (LexerError::RecursionLimit, "recursion_limit"),
+            (
+                LexerError::UnmatchedParen {
+                    open_line: 1,
+                    open_column: 1,
+                },
+                "unmatched_paren",
+            ),
+            (
+                LexerError::UnexpectedOperator {
+                    operator: String::from("*"),
+                    line: 1,
+                    column: 1,
+                },
+                "unexpected_operator",
+            ),
+            (
+                LexerError::NumberAfterIdentifier {
+                    identifier: String::from("x"),
+                    number: String::from("3"),
+                },
+                "number_after_identifier",
+            ),
+            (
+                LexerError::NumberAfterGroup {
+                    number: String::from("2"),
+                },
+                "number_after_group",
+            ),
+        ];
+
+        for (error, code) in cases {
+            assert_eq!(error.code(), code);
+        }
+    }
+}
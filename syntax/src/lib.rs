@@ -1,8 +1,33 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod diagnostic;
+#[cfg(feature = "std")]
 mod evaluator;
 mod expression;
 mod lexer;
+#[cfg(feature = "std")]
+mod rational;
+#[cfg(feature = "std")]
 mod simplifier;
+#[cfg(feature = "std")]
+mod solver;
 mod tokenizer;
 
-pub use simplifier::CanonicalEquation;
-pub use simplifier::Simplifier;
+pub use diagnostic::render_error_location;
+#[cfg(feature = "std")]
+pub use evaluator::{EquationSide, Evaluator, EvaluatorError, EvaluatorErrorType, Value};
+pub use expression::{Expression, ExpressionType};
+pub use lexer::{Equation, Lexer, LexerError, Relation};
+#[cfg(feature = "std")]
+pub use rational::{Coefficient, Rational};
+#[cfg(feature = "std")]
+pub use simplifier::{
+    CanonicalEquation, CanonicalInequality, Simplifier, SimplifierError, Warning, evaluate,
+};
+#[cfg(feature = "std")]
+pub use solver::{LuFactorization, Solver, SolverError, SystemClass, SystemInfo};
+pub use tokenizer::{
+    ByteSource, SliceSource, Token, TokenType, Tokenizer, TokenizerError, reconstruct,
+};
@@ -0,0 +1,301 @@
+use std::fmt::{Display, Formatter};
+
+/// The largest power of ten tried when converting a float into a fraction.
+const MAX_DECIMAL_DIGITS: u32 = 12;
+
+/// An exact fraction, always kept in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i64,
+    denominator: i64,
+}
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+impl Rational {
+    pub fn new(numerator: i64, denominator: i64) -> Option<Self> {
+        Self::reduce(numerator as i128, denominator as i128)
+    }
+
+    pub fn from_integer(value: i64) -> Self {
+        Self {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+
+    /// Converts a float with a short, terminating decimal expansion (such as
+    /// `0.25` or `12.5`) into its exact fraction.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+
+        let mut denominator = 1i64;
+        for _ in 0..=MAX_DECIMAL_DIGITS {
+            let scaled = value * denominator as f64;
+            if scaled.fract() == 0.0 && scaled.abs() < i64::MAX as f64 {
+                return Self::new(scaled as i64, denominator);
+            }
+            denominator *= 10;
+        }
+
+        None
+    }
+
+    fn reduce(numerator: i128, denominator: i128) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+
+        let divisor = gcd(numerator, denominator).max(1) * denominator.signum();
+
+        Some(Self {
+            numerator: (numerator / divisor).try_into().ok()?,
+            denominator: (denominator / divisor).try_into().ok()?,
+        })
+    }
+
+    pub fn numerator(&self) -> i64 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i64 {
+        self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        Self::reduce(
+            self.numerator as i128 * other.denominator as i128
+                + other.numerator as i128 * self.denominator as i128,
+            self.denominator as i128 * other.denominator as i128,
+        )
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_add(other.checked_neg()?)
+    }
+
+    /// `-self`, or `None` for a numerator of `i64::MIN`, whose negation does not
+    /// fit.
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Self {
+            numerator: self.numerator.checked_neg()?,
+            denominator: self.denominator,
+        })
+    }
+
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        Self::reduce(
+            self.numerator as i128 * other.numerator as i128,
+            self.denominator as i128 * other.denominator as i128,
+        )
+    }
+
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        Self::reduce(
+            self.numerator as i128 * other.denominator as i128,
+            self.denominator as i128 * other.numerator as i128,
+        )
+    }
+
+    /// Raises to `exponent` by repeated squaring, so even huge exponents take a
+    /// few dozen multiplications.
+    pub fn checked_pow(self, exponent: i32) -> Option<Self> {
+        let mut base = if exponent < 0 {
+            Self::from_integer(1).checked_div(self)?
+        } else {
+            self
+        };
+
+        let mut result = Self::from_integer(1);
+        let mut exponent = exponent.unsigned_abs();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.checked_mul(base)?;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = base.checked_mul(base)?;
+            }
+        }
+
+        Some(result)
+    }
+
+    pub fn to_latex(&self) -> String {
+        if self.is_integer() {
+            return format!("{}", self.numerator);
+        }
+
+        let sign = if self.numerator < 0 { "-" } else { "" };
+        format!(
+            "{sign}\\frac{{{}}}{{{}}}",
+            self.numerator.unsigned_abs(),
+            self.denominator
+        )
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+/// A coefficient of a canonical equation, which is exact whenever the input
+/// only involved rational arithmetic.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Coefficient {
+    Real(f64),
+    Rational(Rational),
+}
+
+impl Coefficient {
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            Self::Real(value) => *value,
+            Self::Rational(rational) => rational.to_f64(),
+        }
+    }
+
+    /// The exact fraction, or `None` for a [`Coefficient::Real`].
+    pub fn to_rational(&self) -> Option<Rational> {
+        match self {
+            Self::Real(_) => None,
+            Self::Rational(rational) => Some(*rational),
+        }
+    }
+
+    pub fn to_latex(&self) -> String {
+        match self {
+            Self::Real(value) => format!("{value}"),
+            Self::Rational(rational) => rational.to_latex(),
+        }
+    }
+}
+
+impl Display for Coefficient {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Real(value) => write!(f, "{value}"),
+            Self::Rational(rational) => write!(f, "{rational}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rational::{Coefficient, Rational};
+
+    #[test]
+    fn test_normalization() {
+        let half = Rational::new(2, -4).unwrap();
+
+        assert_eq!(half.numerator(), -1);
+        assert_eq!(half.denominator(), 2);
+        assert_eq!(Rational::new(1, 0), None);
+        assert_eq!(Rational::new(0, 5).unwrap(), Rational::from_integer(0));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let third = Rational::new(1, 3).unwrap();
+        let half = Rational::new(1, 2).unwrap();
+
+        assert_eq!(third.checked_add(half), Rational::new(5, 6));
+        assert_eq!(third.checked_sub(half), Rational::new(-1, 6));
+        assert_eq!(third.checked_mul(half), Rational::new(1, 6));
+        assert_eq!(third.checked_div(half), Rational::new(2, 3));
+        assert_eq!(half.checked_pow(-2), Rational::new(4, 1));
+        assert_eq!(third.checked_div(Rational::from_integer(0)), None);
+    }
+
+    #[test]
+    fn test_pow() {
+        let two = Rational::from_integer(2);
+        let one = Rational::from_integer(1);
+
+        assert_eq!(two.checked_pow(10), Rational::new(1024, 1));
+        assert_eq!(two.checked_pow(-3), Rational::new(1, 8));
+        assert_eq!(two.checked_pow(0), Some(one));
+        assert_eq!(two.checked_pow(62), Some(Rational::from_integer(1 << 62)));
+        assert_eq!(two.checked_pow(63), None);
+
+        // These would take billions of multiplications one at a time.
+        assert_eq!(one.checked_pow(i32::MAX), Some(one));
+        assert_eq!(
+            Rational::from_integer(-1).checked_pow(i32::MAX),
+            Rational::new(-1, 1)
+        );
+        assert_eq!(Rational::from_integer(-1).checked_pow(i32::MIN), Some(one));
+        assert_eq!(
+            Rational::from_integer(0).checked_pow(i32::MAX),
+            Some(Rational::from_integer(0))
+        );
+        assert_eq!(Rational::from_integer(0).checked_pow(-1), None);
+    }
+
+    #[test]
+    fn test_min_numerator() {
+        let min = Rational::from_integer(i64::MIN);
+
+        assert_eq!(min.checked_neg(), None);
+        assert_eq!(Rational::from_integer(0).checked_sub(min), None);
+        assert_eq!(min.checked_sub(Rational::from_integer(1)), None);
+        assert_eq!(
+            Rational::new(-1, 3).unwrap().checked_neg(),
+            Rational::new(1, 3)
+        );
+        assert_eq!(
+            Rational::new(i64::MIN, 3).unwrap().to_latex(),
+            "-\\frac{9223372036854775808}{3}"
+        );
+    }
+
+    #[test]
+    fn test_from_f64() {
+        assert_eq!(Rational::from_f64(0.25), Rational::new(1, 4));
+        assert_eq!(Rational::from_f64(-12.5), Rational::new(-25, 2));
+        assert_eq!(Rational::from_f64(1.0 / 3.0), None);
+    }
+
+    #[test]
+    fn test_display() {
+        let coefficient = Coefficient::Rational(Rational::new(-1, 3).unwrap());
+
+        assert_eq!(format!("{coefficient}"), "-1/3");
+        assert_eq!(coefficient.to_latex(), "-\\frac{1}{3}");
+        assert_eq!(Coefficient::Real(0.5).to_latex(), "0.5");
+        assert_eq!(Rational::from_integer(4).to_latex(), "4");
+    }
+
+    #[test]
+    fn test_to_rational() {
+        let third = Rational::new(1, 3).unwrap();
+
+        assert_eq!(Coefficient::Rational(third).to_rational(), Some(third));
+        assert_eq!(Coefficient::Real(0.5).to_rational(), None);
+    }
+}
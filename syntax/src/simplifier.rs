@@ -1,92 +1,893 @@
-use std::collections::HashMap;
-use std::io::Cursor;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::{BufRead, Cursor};
+use std::ops::{Add, Sub};
+use std::str::FromStr;
 
 use crate::{
-    evaluator::{Evaluator, Value},
-    lexer::Lexer,
+    evaluator::{EquationSide, Evaluator, Value},
+    lexer::{Equation, Lexer, Relation},
+    rational::{Coefficient, Rational},
     simplifier::error::SimplifierResult,
-    tokenizer::Tokenizer,
+    tokenizer::{Token, TokenType, Tokenizer},
 };
 mod error;
+mod warning;
 
-pub struct Simplifier();
+pub use error::SimplifierError;
+pub use warning::Warning;
 
-pub struct CanonicalEquation {
+/// Turns equations written by users into their [`CanonicalEquation`].
+///
+/// Every option is set with a builder method on top of [`Simplifier::new`], and
+/// is off by default, so a default simplifier behaves like the stateless one
+/// from before there were any options.
+#[derive(Debug, Clone, Default)]
+pub struct Simplifier {
+    assume_zero_rhs: bool,
+    case_insensitive_variables: bool,
+}
+
+/// A linear equation in the form `sum(terms) = constant`.
+///
+/// Every variable term is moved to the left-hand side and every constant to the
+/// right-hand side, so `2x + 1 = y` is stored as `terms = {x: 2, y: -1}` and
+/// `constant = -1`.
+///
+/// Coefficients are `f64` by default; [`Simplifier::simplify_equation_exact`]
+/// produces a `CanonicalEquation<Coefficient>` keeping fractions exact.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanonicalEquation<C = f64> {
+    pub terms: HashMap<String, C>,
+    pub constant: C,
+}
+
+impl CanonicalEquation {
+    /// The constant on the right-hand side in textbook form, so `3x + 2y = 7`
+    /// gives `7`. This is [`CanonicalEquation::constant`] itself, which is already
+    /// kept on the right rather than moved next to the terms.
+    pub fn rhs(&self) -> f64 {
+        self.constant
+    }
+
+    /// The terms on the left-hand side in textbook form, sorted by variable, so
+    /// `3x + 2y = 7` gives `[("x", 3), ("y", 2)]`.
+    pub fn lhs_terms(&self) -> Vec<(&str, f64)> {
+        let mut terms: Vec<(&str, f64)> = self
+            .terms
+            .iter()
+            .map(|(variable, coefficient)| (variable.as_str(), *coefficient))
+            .collect();
+        terms.sort_by(|a, b| a.0.cmp(b.0));
+
+        terms
+    }
+
+    /// The coefficient of each of `variables`, in that order, with `0.0` for the
+    /// ones missing from this equation. This is the equation's row in a matrix
+    /// whose columns are `variables`, without the constant.
+    pub fn dense_terms(&self, variables: &[String]) -> Vec<f64> {
+        variables
+            .iter()
+            .map(|variable| self.terms.get(variable).copied().unwrap_or(0.0))
+            .collect()
+    }
+
+    /// Every coefficient that differs between both equations, as `self - other`,
+    /// sorted by variable. A variable missing from one side counts as zero. The
+    /// constant, if it differs, comes last under an empty name.
+    pub fn diff(&self, other: &Self) -> Vec<(String, f64)> {
+        let variables: BTreeSet<&String> = self.terms.keys().chain(other.terms.keys()).collect();
+        let coefficient = |equation: &Self, variable: &String| {
+            equation.terms.get(variable).copied().unwrap_or(0.0)
+        };
+
+        let mut differences: Vec<(String, f64)> = variables
+            .into_iter()
+            .map(|variable| {
+                let difference = coefficient(self, variable) - coefficient(other, variable);
+                (variable.clone(), difference)
+            })
+            .collect();
+        differences.push((String::new(), self.constant - other.constant));
+        differences.retain(|(_, difference)| *difference != 0.0);
+
+        differences
+    }
+
+    /// The terms sorted by variable and the constant, in the same `sum(terms) =
+    /// constant` form. Unlike [`CanonicalEquation::terms`], the order is always the
+    /// same, so this is the shape to serialize or hand to other languages.
+    pub fn to_parts(&self) -> (BTreeMap<String, f64>, f64) {
+        let terms = self
+            .terms
+            .iter()
+            .map(|(variable, coefficient)| (variable.clone(), *coefficient))
+            .collect();
+
+        (terms, self.constant)
+    }
+
+    /// Returns the equation with everything moved to the left-hand side, so that
+    /// it reads `sum(terms) + constant = 0`. The returned constant is therefore
+    /// the negation of [`CanonicalEquation::constant`].
+    pub fn as_homogeneous(&self) -> (BTreeMap<String, f64>, f64) {
+        let terms = self
+            .terms
+            .iter()
+            .map(|(variable, coefficient)| (variable.clone(), *coefficient))
+            .collect();
+
+        (terms, -self.constant)
+    }
+
+    /// Flips the sign of every coefficient and of the constant, which describes
+    /// the same equation multiplied by `-1`.
+    pub fn negate(&mut self) {
+        for coefficient in self.terms.values_mut() {
+            *coefficient = -*coefficient;
+        }
+        self.constant = -self.constant;
+    }
+
+    /// Multiplies every coefficient and the constant by `factor`, like the row
+    /// operation `R *= factor`. Scaling by zero leaves the zero equation, with no
+    /// terms and a zero constant.
+    pub fn scale(&mut self, factor: f64) {
+        if factor == 1.0 {
+            return;
+        }
+
+        if factor == 0.0 {
+            self.terms.clear();
+            self.constant = 0.0;
+            return;
+        }
+
+        for coefficient in self.terms.values_mut() {
+            *coefficient *= factor;
+        }
+        self.constant *= factor;
+    }
+
+    /// Rounds every coefficient and the constant to `decimals` decimal places, for
+    /// display, so `0.30000000000000004` shows as `0.3`. Coefficients that round
+    /// to zero are kept.
+    pub fn round_to(&mut self, decimals: u32) {
+        let factor = 10f64.powi(decimals.min(f64::MAX_10_EXP as u32) as i32);
+        // Values too large to scale already have no decimals left to round.
+        let round = |value: f64| match value * factor {
+            scaled if scaled.is_finite() => scaled.round() / factor,
+            _ => value,
+        };
+
+        for coefficient in self.terms.values_mut() {
+            *coefficient = round(*coefficient);
+        }
+        self.constant = round(self.constant);
+    }
+
+    /// Writes the equation with every number in scientific notation rounded to
+    /// `sig_figs` significant figures, so `0.0000123x = 2` with three figures
+    /// reads `1.23e-5x = 2.00e0`. Terms are sorted by variable and zero
+    /// coefficients skipped. At least one significant figure is always shown.
+    pub fn to_string_scientific(&self, sig_figs: usize) -> String {
+        let precision = sig_figs.saturating_sub(1);
+        format_equation_with(
+            self.terms
+                .iter()
+                .map(|(variable, coefficient)| (variable, *coefficient)),
+            self.constant,
+            |value| format!("{value:.precision$e}"),
+        )
+    }
+
+    /// Writes the equation as an s-expression, so `2x - y = 3` reads
+    /// `(= (+ (* 2 x) (* -1 y)) 3)`. Terms are sorted by variable and zero
+    /// coefficients skipped.
+    pub fn to_sexpr(&self) -> String {
+        let (terms, constant) = self.display_parts();
+        let products: Vec<String> = terms
+            .iter()
+            .map(|(variable, coefficient)| format!("(* {coefficient} {variable})"))
+            .collect();
+        let left = match products.as_slice() {
+            [] => String::from("0"),
+            [product] => product.clone(),
+            _ => format!("(+ {})", products.join(" ")),
+        };
+
+        format!("(= {left} {constant})")
+    }
+
+    /// Writes the equation the way it would be typed, like `2x - y = 3`, leaving
+    /// out unit coefficients. Terms are sorted by variable and zero coefficients
+    /// skipped.
+    pub fn to_infix(&self) -> String {
+        self.write_infix(|variable| variable.to_string())
+    }
+
+    /// Like [`CanonicalEquation::to_infix`], writing what follows the first `_` of
+    /// a variable as a subscript, so `x_1` reads `x_{1}`.
+    pub fn to_latex(&self) -> String {
+        self.write_infix(|variable| match variable.split_once('_') {
+            Some((base, subscript)) if !base.is_empty() && !subscript.is_empty() => {
+                format!("{base}_{{{subscript}}}")
+            }
+            _ => variable.replace('_', "\\_"),
+        })
+    }
+
+    /// Writes the equation as a JSON object, so `2x - y = 3` reads
+    /// `{"terms":{"x":2,"y":-1},"constant":3}`. Terms are sorted by variable and
+    /// zero coefficients skipped. Variable names are ASCII letters, digits and
+    /// underscores, so they never need escaping.
+    pub fn to_json(&self) -> String {
+        let (terms, constant) = self.display_parts();
+        let terms: Vec<String> = terms
+            .iter()
+            .map(|(variable, coefficient)| format!("\"{variable}\":{coefficient}"))
+            .collect();
+
+        format!(
+            "{{\"terms\":{{{}}},\"constant\":{constant}}}",
+            terms.join(",")
+        )
+    }
+
+    /// Writes `2x - y = 3`, naming each variable with `variable`.
+    fn write_infix(&self, variable: impl Fn(&str) -> String) -> String {
+        let (terms, constant) = self.display_parts();
+
+        let mut out = String::new();
+        for (index, (name, coefficient)) in terms.iter().enumerate() {
+            let sign = match (index, *coefficient < 0.0) {
+                (0, true) => "-",
+                (0, false) => "",
+                (_, true) => " - ",
+                (_, false) => " + ",
+            };
+            let magnitude = coefficient.abs();
+            let magnitude = if magnitude == 1.0 {
+                String::new()
+            } else {
+                magnitude.to_string()
+            };
+
+            out.push_str(&format!("{sign}{magnitude}{}", variable(name)));
+        }
+        if out.is_empty() {
+            out.push('0');
+        }
+
+        format!("{out} = {constant}")
+    }
+
+    /// The terms to print, sorted by variable and without zero coefficients, and
+    /// the constant. Adding zero turns a `-0` into `0`, so it prints the same.
+    fn display_parts(&self) -> (Vec<(&str, f64)>, f64) {
+        let terms = self
+            .lhs_terms()
+            .into_iter()
+            .filter(|(_, coefficient)| *coefficient != 0.0)
+            .map(|(variable, coefficient)| (variable, coefficient + 0.0))
+            .collect();
+
+        (terms, self.constant + 0.0)
+    }
+
+    /// Replaces `variable` with `value`, dropping its term and moving its product
+    /// into the constant, so `y = 2` turns `x + 3y = 10` into `x = 4`. Does nothing
+    /// if the equation has no `variable`.
+    pub fn substitute(&mut self, variable: &str, value: f64) {
+        if let Some(coefficient) = self.terms.remove(variable) {
+            self.constant -= coefficient * value;
+        }
+    }
+
+    /// Whether the equation holds for any value of its variables, like `2 + 3 = 5`
+    /// or `x = x`.
+    pub fn is_identity(&self) -> bool {
+        self.terms.values().all(|coefficient| *coefficient == 0.0) && self.constant == 0.0
+    }
+
+    /// Whether the equation holds for no value of its variables, like `2 + 3 = 6`.
+    pub fn is_contradiction(&self) -> bool {
+        self.terms.values().all(|coefficient| *coefficient == 0.0) && self.constant != 0.0
+    }
+
+    /// Solves for `variable` after substituting the `known` values of every other
+    /// variable. Returns `None` if the coefficient of `variable` is zero or some
+    /// other variable has no known value.
+    pub fn solve_for(&self, variable: &str, known: &HashMap<String, f64>) -> Option<f64> {
+        let coefficient = self.terms.get(variable).copied().unwrap_or(0.0);
+        if coefficient == 0.0 {
+            return None;
+        }
+
+        let mut constant = self.constant;
+        for (name, other) in self.terms.iter().filter(|(name, _)| *name != variable) {
+            constant -= other * known.get(name)?;
+        }
+
+        Some(constant / coefficient)
+    }
+
+    /// Returns a stable string shared by every multiple of the same equation, such
+    /// as `2x + 4 = 0` and `-x - 2 = 0`, meant for deduplicating and caching.
+    ///
+    /// Terms are sorted by name and zero coefficients dropped. The sign is flipped
+    /// so the first term is positive and, when every number is an integer, the
+    /// equation is divided by their greatest common divisor.
+    pub fn canonical_key(&self) -> String {
+        let terms: BTreeMap<&String, f64> = self
+            .terms
+            .iter()
+            .filter(|(_, coefficient)| **coefficient != 0.0)
+            .map(|(variable, coefficient)| (variable, *coefficient))
+            .collect();
+
+        let leading = terms.values().next().copied().unwrap_or(self.constant);
+        let mut divisor = if leading < 0.0 { -1.0 } else { 1.0 };
+        if let Some(gcd) = integer_gcd(terms.values().chain([&self.constant])) {
+            divisor *= gcd;
+        }
+
+        format_equation(
+            terms
+                .into_iter()
+                .map(|(variable, coefficient)| (variable, coefficient / divisor)),
+            self.constant / divisor,
+        )
+    }
+}
+
+/// Writes `terms` sorted by variable, skipping zero coefficients, as in
+/// `2x + -1y = 3`.
+fn format_equation<'a>(terms: impl Iterator<Item = (&'a String, f64)>, constant: f64) -> String {
+    format_equation_with(terms, constant, |value| value.to_string())
+}
+
+/// Like [`format_equation`], writing every number with `format`.
+fn format_equation_with<'a>(
+    terms: impl Iterator<Item = (&'a String, f64)>,
+    constant: f64,
+    format: impl Fn(f64) -> String,
+) -> String {
+    let terms: BTreeMap<&String, f64> = terms.filter(|(_, c)| *c != 0.0).collect();
+
+    // Adding zero turns a `-0` into `0`, so it prints the same.
+    let terms: Vec<String> = terms
+        .iter()
+        .map(|(variable, coefficient)| format!("{}{variable}", format(coefficient + 0.0)))
+        .collect();
+    format!("{} = {}", terms.join(" + "), format(constant + 0.0))
+}
+
+/// The greatest common divisor of `values` when they are all integers and not all
+/// zero.
+fn integer_gcd<'a>(values: impl Iterator<Item = &'a f64>) -> Option<f64> {
+    let mut gcd = 0i64;
+    for value in values {
+        if value.fract() != 0.0 || value.abs() >= i64::MAX as f64 {
+            return None;
+        }
+
+        let (mut a, mut b) = (gcd, (*value as i64).abs());
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        gcd = a;
+    }
+
+    (gcd != 0).then_some(gcd as f64)
+}
+
+impl FromStr for CanonicalEquation {
+    type Err = SimplifierError;
+
+    /// Parses with a default [`Simplifier`].
+    fn from_str(input: &str) -> SimplifierResult<Self> {
+        Simplifier::new().simplify_equation(input)
+    }
+}
+
+/// Adds both equations side by side. Variables whose coefficients cancel are
+/// dropped, so they do not count as variables of the result.
+impl Add for CanonicalEquation {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        for (variable, coefficient) in other.terms {
+            *self.terms.entry(variable).or_default() += coefficient;
+        }
+        self.terms.retain(|_, coefficient| *coefficient != 0.0);
+        self.constant += other.constant;
+
+        self
+    }
+}
+
+/// Subtracts `other` side by side. Variables whose coefficients cancel are
+/// dropped, so `a - a` has no terms at all.
+impl Sub for CanonicalEquation {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self {
+        for (variable, coefficient) in other.terms {
+            *self.terms.entry(variable).or_default() -= coefficient;
+        }
+        self.terms.retain(|_, coefficient| *coefficient != 0.0);
+        self.constant -= other.constant;
+
+        self
+    }
+}
+
+impl CanonicalEquation<Coefficient> {
+    /// Converts the equation to exact fractions, as taken by
+    /// [`crate::Solver::solve_exact`], or `None` when any coefficient is a
+    /// [`Coefficient::Real`].
+    pub fn to_rational(&self) -> Option<CanonicalEquation<Rational>> {
+        Some(CanonicalEquation {
+            terms: self
+                .terms
+                .iter()
+                .map(|(variable, coefficient)| Some((variable.clone(), coefficient.to_rational()?)))
+                .collect::<Option<_>>()?,
+            constant: self.constant.to_rational()?,
+        })
+    }
+}
+
+impl Equation {
+    /// Whether both equations have the same solutions: their canonical forms
+    /// are multiples of one another, no matter how the terms are ordered or
+    /// scaled. Equations that fail to simplify, and inequalities, are never
+    /// equivalent.
+    pub fn is_equivalent(&self, other: &Equation) -> bool {
+        if self.relation != Relation::Eq || other.relation != Relation::Eq {
+            return false;
+        }
+
+        let simplifier = Simplifier::new();
+        let evaluator = Evaluator::new();
+        let (Ok(left), Ok(right)) = (
+            simplifier.canonicalize(&evaluator, self),
+            simplifier.canonicalize(&evaluator, other),
+        ) else {
+            return false;
+        };
+
+        let (left_terms, left_constant) = normalize(&left.0, left.1);
+        let (right_terms, right_constant) = normalize(&right.0, right.1);
+        let close = |a: f64, b: f64| (a - b).abs() <= EQUIVALENCE_EPSILON * a.abs().max(1.0);
+
+        left_terms.len() == right_terms.len()
+            && left_terms
+                .iter()
+                .zip(&right_terms)
+                .all(|(l, r)| l.0 == r.0 && close(*l.1, *r.1))
+            && close(left_constant, right_constant)
+    }
+}
+
+/// Relative tolerance when comparing normalized coefficients.
+const EQUIVALENCE_EPSILON: f64 = 1e-9;
+
+/// Divides the equation by its first non-zero coefficient, taking terms sorted
+/// by variable and then the constant, so every multiple of an equation yields
+/// the same result. Zero coefficients are dropped.
+fn normalize(terms: &HashMap<String, f64>, constant: f64) -> (BTreeMap<&str, f64>, f64) {
+    let terms: BTreeMap<&str, f64> = terms
+        .iter()
+        .filter(|(_, coefficient)| **coefficient != 0.0)
+        .map(|(variable, coefficient)| (variable.as_str(), *coefficient))
+        .collect();
+
+    let leading = terms.values().next().copied().unwrap_or(constant);
+    if leading == 0.0 {
+        return (terms, constant);
+    }
+
+    let terms = terms
+        .into_iter()
+        .map(|(variable, coefficient)| (variable, coefficient / leading))
+        .collect();
+
+    (terms, constant / leading)
+}
+
+/// An inequality in the form `sum(terms) relation constant`, following the same
+/// sign convention as [`CanonicalEquation`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanonicalInequality {
     pub terms: HashMap<String, f64>,
     pub constant: f64,
+    pub relation: Relation,
+}
+
+/// Parses a single expression, without any relation, and evaluates it to a
+/// [`Value`], keeping its polynomial form instead of a canonical map.
+pub fn evaluate(input: &str) -> SimplifierResult<Value> {
+    let mut lexer = Lexer::new(Simplifier::new().tokenize(input)?);
+    let expression = lexer.expression_only()?;
+
+    Ok(Evaluator::new().evaluate_expression(&expression)?)
 }
 
 impl Simplifier {
+    /// A simplifier with every option off, the same as [`Simplifier::default`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// When enabled, an input without any relation, like `2x + 3y`, is read as if
+    /// it ended in `= 0` instead of failing to parse. Disabled by default.
+    ///
+    /// This only applies to equations and inequalities; [`evaluate`] already
+    /// parses a lone expression and is not affected.
+    pub fn assume_zero_rhs(mut self, assume_zero_rhs: bool) -> Self {
+        self.assume_zero_rhs = assume_zero_rhs;
+        self
+    }
+
+    /// When enabled, variables that only differ by case are the same, so
+    /// `X + x = 2` becomes `2x = 2`. Canonical terms are keyed by the lowercase
+    /// name. Disabled by default, keeping `X` and `x` apart.
+    pub fn case_insensitive_variables(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive_variables = case_insensitive;
+        self
+    }
+
     pub fn simplify_equation(&self, user_input: &str) -> SimplifierResult<CanonicalEquation> {
-        let mut terms = HashMap::new();
-        let mut constant = 0.0f64;
+        self.simplify_equation_with(&Evaluator::new(), user_input)
+    }
+
+    /// Simplifies an equation and also returns its parse tree, for showing both
+    /// views without parsing twice.
+    pub fn parse_and_simplify(
+        &self,
+        user_input: &str,
+    ) -> SimplifierResult<(Equation, CanonicalEquation)> {
+        let mut lexer = Lexer::new(self.tokenize(user_input)?);
+        let equation = lexer.equation()?;
+
+        let (terms, constant) = self.canonicalize(&Evaluator::new(), &equation)?;
+
+        Ok((equation, CanonicalEquation { terms, constant }))
+    }
+
+    /// Simplifies each side of the equation on its own, as if each were
+    /// `side = 0`, before both get combined. `2x + 3x = y + 2 + y` gives `5x = 0`
+    /// and `2y = -2`.
+    pub fn canonicalize_sides(
+        &self,
+        user_input: &str,
+    ) -> SimplifierResult<(CanonicalEquation, CanonicalEquation)> {
+        let mut lexer = Lexer::new(self.tokenize(user_input)?);
+        let equation = lexer.equation()?;
+
+        let (left, right) = self.evaluate_sides(&Evaluator::new(), &equation)?;
+        let side = |value: Value| {
+            let (terms, constant) = self.combine_sides(value, Value::new_constant(0.0));
+            CanonicalEquation { terms, constant }
+        };
+
+        Ok((side(left), side(right)))
+    }
+
+    /// Moves every term of the equation to the left, returning `left - right` as a
+    /// [`Value`] with like terms combined but before it becomes a canonical map.
+    pub fn simplify_to_value(&self, user_input: &str) -> SimplifierResult<Value> {
+        let mut lexer = Lexer::new(self.tokenize(user_input)?);
+        let equation = lexer.equation()?;
+
+        Ok(Evaluator::new().evaluate_equation(&equation)?)
+    }
+
+    /// Simplifies an equation and describes how it got there, for teaching. The
+    /// steps are the parsed equation in infix notation, both sides after
+    /// distributing products, and the final canonical form.
+    pub fn explain(&self, user_input: &str) -> SimplifierResult<(CanonicalEquation, Vec<String>)> {
+        let mut lexer = Lexer::new(self.tokenize(user_input)?);
+        let equation = lexer.equation()?;
+        let mut steps = vec![format!(
+            "Parsed: {} = {}",
+            equation.left.to_infix(),
+            equation.right.to_infix()
+        )];
+
+        let (left, right) = self.evaluate_sides(&Evaluator::new(), &equation)?;
+        steps.push(format!("Expanded: {left} = {right}"));
+
+        let (terms, constant) = self.combine_sides(left, right);
+        steps.push(format!(
+            "Combined: {}",
+            format_equation(terms.iter().map(|(variable, c)| (variable, *c)), constant)
+        ));
+
+        Ok((CanonicalEquation { terms, constant }, steps))
+    }
+
+    /// Simplifies an equation read straight from `reader`, without collecting the
+    /// input into a `String` first. Error columns count bytes within the current
+    /// line of the stream.
+    pub fn simplify_reader<R: BufRead>(&self, reader: R) -> SimplifierResult<CanonicalEquation> {
+        let mut lexer = Lexer::new(self.tokenize_reader(reader)?);
+        let equation = lexer.equation()?;
+
+        let (terms, constant) = self.canonicalize(&Evaluator::new(), &equation)?;
+
+        Ok(CanonicalEquation { terms, constant })
+    }
+
+    /// Simplifies an equation and also reports coefficients, or a constant, whose
+    /// magnitude is so large or so small that the result is likely imprecise.
+    /// Warnings are sorted by variable, with the constant last.
+    pub fn simplify_equation_checked(
+        &self,
+        user_input: &str,
+    ) -> SimplifierResult<(CanonicalEquation, Vec<Warning>)> {
+        let equation = self.simplify_equation(user_input)?;
+
+        let mut warnings: Vec<Warning> = equation
+            .lhs_terms()
+            .into_iter()
+            .filter_map(|(variable, coefficient)| Warning::check(Some(variable), coefficient))
+            .collect();
+        warnings.extend(Warning::check(None, equation.constant));
+
+        Ok((equation, warnings))
+    }
+
+    /// Simplifies an equation like [`Simplifier::simplify_equation`], writing its
+    /// terms into `terms` and returning the constant. The map is cleared on entry,
+    /// so a single buffer can be reused across many calls without reallocating.
+    pub fn simplify_equation_into(
+        &self,
+        user_input: &str,
+        terms: &mut HashMap<String, f64>,
+    ) -> SimplifierResult<f64> {
+        terms.clear();
+        let mut lexer = Lexer::new(self.tokenize(user_input)?);
+        let equation = lexer.equation()?;
+
+        self.canonicalize_into(&Evaluator::new(), &equation, terms)
+    }
+
+    /// Simplifies every input independently, sharing a single [`Evaluator`]
+    /// between them. An invalid input does not prevent the rest from being
+    /// simplified.
+    pub fn simplify_many(&self, inputs: &[&str]) -> Vec<SimplifierResult<CanonicalEquation>> {
+        let evaluator = Evaluator::new();
+
+        inputs
+            .iter()
+            .map(|input| self.simplify_equation_with(&evaluator, input))
+            .collect()
+    }
+
+    fn simplify_equation_with(
+        &self,
+        evaluator: &Evaluator,
+        user_input: &str,
+    ) -> SimplifierResult<CanonicalEquation> {
+        let mut lexer = Lexer::new(self.tokenize(user_input)?);
+        let equation = lexer.equation()?;
+
+        let (terms, constant) = self.canonicalize(evaluator, &equation)?;
+
+        Ok(CanonicalEquation { terms, constant })
+    }
+
+    pub fn simplify_inequality(&self, user_input: &str) -> SimplifierResult<CanonicalInequality> {
+        let mut lexer = Lexer::new(self.tokenize(user_input)?);
+        let equation = lexer.relation()?;
+
+        let (terms, constant) = self.canonicalize(&Evaluator::new(), &equation)?;
+
+        Ok(CanonicalInequality {
+            terms,
+            constant,
+            relation: equation.relation,
+        })
+    }
 
-        let tokenizer = Tokenizer::new(Cursor::new(user_input));
+    /// Simplifies an equation like [`Simplifier::simplify_equation`], but keeps
+    /// every coefficient as an exact [`Coefficient::Rational`] when the input only
+    /// involves rational arithmetic, so `(1/3)x = 1` yields `1/3` instead of
+    /// `0.333...`. Otherwise, every coefficient falls back to
+    /// [`Coefficient::Real`].
+    pub fn simplify_equation_exact(
+        &self,
+        user_input: &str,
+    ) -> SimplifierResult<CanonicalEquation<Coefficient>> {
+        let mut lexer = Lexer::new(self.tokenize(user_input)?);
+        let equation = lexer.equation()?;
+
+        let evaluator = Evaluator::new();
+        let (terms, constant) = self.canonicalize(&evaluator, &equation)?;
+
+        let exact = self.canonicalize_rational(&evaluator, &equation);
+        let equation = match exact {
+            Some((terms, constant)) => CanonicalEquation {
+                terms: terms
+                    .into_iter()
+                    .map(|(variable, coefficient)| (variable, Coefficient::Rational(coefficient)))
+                    .collect(),
+                constant: Coefficient::Rational(constant),
+            },
+            None => CanonicalEquation {
+                terms: terms
+                    .into_iter()
+                    .map(|(variable, coefficient)| (variable, Coefficient::Real(coefficient)))
+                    .collect(),
+                constant: Coefficient::Real(constant),
+            },
+        };
+
+        Ok(equation)
+    }
+
+    fn tokenize(&self, user_input: &str) -> SimplifierResult<Vec<Token>> {
+        self.tokenize_reader(Cursor::new(user_input))
+    }
+
+    fn tokenize_reader<R: BufRead>(&self, reader: R) -> SimplifierResult<Vec<Token>> {
+        let tokenizer = Tokenizer::new(reader);
         let mut tokens = Vec::new();
 
         for token in tokenizer {
             tokens.push(token?);
         }
 
-        let mut lexer = Lexer::new(tokens);
+        if self.assume_zero_rhs && !tokens.iter().any(Token::is_relation) {
+            let (line, column) = tokens.last().map_or((1, 1), |last| {
+                (last.line, last.column + last.lexeme.len() + 1)
+            });
 
-        let equation = lexer.equation()?;
+            tokens.push(Token::new(TokenType::Equal, String::from("="), column).with_line(line));
+            tokens.push(
+                Token::new(TokenType::Number(0.0), String::from("0"), column + 2).with_line(line),
+            );
+        }
 
-        let evaluator = Evaluator::new();
+        Ok(tokens)
+    }
 
-        let left = evaluator.evaluate_expression(&equation.left)?;
-        let right = evaluator.evaluate_expression(&equation.right)?;
+    fn canonicalize(
+        &self,
+        evaluator: &Evaluator,
+        equation: &Equation,
+    ) -> SimplifierResult<(HashMap<String, f64>, f64)> {
+        let mut terms = HashMap::new();
+        let constant = self.canonicalize_into(evaluator, equation, &mut terms)?;
 
-        match left {
-            Value::Monomial {
-                coefficient,
-                variable,
-            } => match variable {
-                Some(variable) => *terms.entry(variable).or_default() += coefficient,
-                None => constant += coefficient,
-            },
-            Value::Sum(values) => self.simplify_into_map(values, &mut terms, &mut constant, 1.0),
+        Ok((terms, constant))
+    }
+
+    /// Adds the terms of the canonical equation to `terms`, returning its constant.
+    fn canonicalize_into(
+        &self,
+        evaluator: &Evaluator,
+        equation: &Equation,
+        terms: &mut HashMap<String, f64>,
+    ) -> SimplifierResult<f64> {
+        // Equations without variables skip building `Value`s altogether.
+        if !equation.left.has_variables() && !equation.right.has_variables() {
+            let left = evaluator
+                .evaluate_constant(&equation.left)
+                .map_err(|e| e.on_side(EquationSide::Left))?;
+            let right = evaluator
+                .evaluate_constant(&equation.right)
+                .map_err(|e| e.on_side(EquationSide::Right))?;
+
+            if let (Some(left), Some(right)) = (left, right) {
+                return Ok(right - left);
+            }
         }
 
-        match right {
-            Value::Monomial {
-                coefficient,
-                variable,
-            } => match variable {
-                Some(variable) => *terms.entry(variable).or_default() += coefficient,
-                None => constant -= coefficient,
-            },
+        let (left, right) = self.evaluate_sides(evaluator, equation)?;
+
+        Ok(self.combine_sides_into(left, right, terms))
+    }
+
+    fn evaluate_sides(
+        &self,
+        evaluator: &Evaluator,
+        equation: &Equation,
+    ) -> SimplifierResult<(Value, Value)> {
+        let left = evaluator
+            .evaluate_expression(&equation.left)
+            .map_err(|e| e.on_side(EquationSide::Left))?;
+        let right = evaluator
+            .evaluate_expression(&equation.right)
+            .map_err(|e| e.on_side(EquationSide::Right))?;
+
+        Ok((left, right))
+    }
+
+    fn combine_sides(&self, left: Value, right: Value) -> (HashMap<String, f64>, f64) {
+        let mut terms = HashMap::new();
+        let constant = self.combine_sides_into(left, right, &mut terms);
+
+        (terms, constant)
+    }
+
+    fn combine_sides_into(
+        &self,
+        left: Value,
+        right: Value,
+        terms: &mut HashMap<String, f64>,
+    ) -> f64 {
+        let mut constant = 0.0f64;
 
-            Value::Sum(values) => self.simplify_into_map(values, &mut terms, &mut constant, -1.0),
+        self.simplify_into_map(left, terms, &mut constant, 1.0);
+        self.simplify_into_map(right, terms, &mut constant, -1.0);
+
+        constant
+    }
+
+    fn canonicalize_rational(
+        &self,
+        evaluator: &Evaluator,
+        equation: &Equation,
+    ) -> Option<(HashMap<String, Rational>, Rational)> {
+        let left = evaluator.evaluate_rational(&equation.left)?;
+        let right = evaluator.evaluate_rational(&equation.right)?;
+
+        let mut terms: HashMap<String, Rational> = HashMap::new();
+        let mut constant = Rational::from_integer(0);
+
+        let right: Vec<_> = right
+            .into_iter()
+            .map(|(v, c)| Some((v, c.checked_neg()?)))
+            .collect::<Option<_>>()?;
+        for (variable, coefficient) in left.into_iter().chain(right) {
+            match variable {
+                Some(variable) => {
+                    let term = terms
+                        .entry(self.variable_key(variable))
+                        .or_insert(Rational::from_integer(0));
+                    *term = term.checked_add(coefficient)?;
+                }
+                None => constant = constant.checked_sub(coefficient)?,
+            }
         }
 
-        Ok(CanonicalEquation {
-            terms: terms,
-            constant,
-        })
+        Some((terms, constant))
+    }
+
+    fn variable_key(&self, variable: String) -> String {
+        if self.case_insensitive_variables {
+            variable.to_lowercase()
+        } else {
+            variable
+        }
     }
 
     fn simplify_into_map(
         &self,
-        values: Vec<Value>,
+        value: Value,
         terms_map: &mut HashMap<String, f64>,
         constant: &mut f64,
         multiply_by: f64,
     ) {
-        for value in values {
-            match value {
-                Value::Monomial {
-                    coefficient,
-                    variable,
-                } => match variable {
-                    Some(variable) => {
-                        *terms_map.entry(variable).or_default() += coefficient * multiply_by
-                    }
-                    None => *constant += coefficient * multiply_by * -1.0,
-                },
+        match value {
+            Value::Monomial {
+                coefficient,
+                variable,
+            } => match variable {
+                Some(variable) => {
+                    *terms_map.entry(self.variable_key(variable)).or_default() +=
+                        coefficient * multiply_by
+                }
+                None => *constant -= coefficient * multiply_by,
+            },
 
-                Value::Sum(values) => {
-                    self.simplify_into_map(values, terms_map, constant, multiply_by)
+            Value::Sum(values) => {
+                for value in values {
+                    self.simplify_into_map(value, terms_map, constant, multiply_by)
                 }
             }
         }
@@ -95,13 +896,20 @@ impl Simplifier {
 
 #[cfg(test)]
 mod tests {
-    use crate::simplifier::Simplifier;
-    use std::collections::HashMap;
+    use crate::evaluator::EquationSide;
+    use crate::evaluator::Value;
+    use crate::lexer::Relation;
+    use crate::lexer::{Lexer, LexerError};
+    use crate::rational::{Coefficient, Rational};
+    use crate::simplifier::{CanonicalEquation, Simplifier, SimplifierError, Warning, evaluate};
+    use crate::tokenizer::TokenizerError;
+    use std::collections::{BTreeMap, HashMap};
+    use std::io::BufReader;
 
     #[test]
     pub fn test_sums() {
         let expr = "2x + 3x - 2 = x + y + 2";
-        let simplifier = Simplifier();
+        let simplifier = Simplifier::default();
 
         let result = simplifier.simplify_equation(expr).unwrap();
 
@@ -115,7 +923,7 @@ mod tests {
     #[test]
     pub fn test_substraction() {
         let expr = "-2x -6x -3y = -5 -x -y -10";
-        let simplifier = Simplifier();
+        let simplifier = Simplifier::default();
 
         let result = simplifier.simplify_equation(expr).unwrap();
 
@@ -131,7 +939,7 @@ mod tests {
     pub fn test_multiplication() {
         let expr = "3*(x + 2y -5) = -4*(-8y + 10x + 2)";
 
-        let simplifier = Simplifier();
+        let simplifier = Simplifier::default();
 
         let result = simplifier.simplify_equation(expr).unwrap();
 
@@ -147,7 +955,7 @@ mod tests {
     pub fn test_division() {
         let expr = "(24x + 12y + 6)/3 = 0";
 
-        let simplifier = Simplifier();
+        let simplifier = Simplifier::default();
 
         let result = simplifier.simplify_equation(expr).unwrap();
 
@@ -158,4 +966,667 @@ mod tests {
 
         assert_eq!(result.constant, -2.0);
     }
+
+    #[test]
+    pub fn test_single_monomial_sides() {
+        let simplifier = Simplifier::new();
+
+        let result = simplifier.simplify_equation("x = 3").unwrap();
+        assert_eq!(result.terms, HashMap::from([(String::from("x"), 1.0f64)]));
+        assert_eq!(result.constant, 3.0);
+
+        let result = simplifier.simplify_equation("3 = x").unwrap();
+        assert_eq!(result.terms, HashMap::from([(String::from("x"), -1.0f64)]));
+        assert_eq!(result.constant, -3.0);
+    }
+
+    #[test]
+    pub fn test_as_homogeneous() {
+        let expr = "2x + 1 = y + 4";
+        let simplifier = Simplifier::new();
+
+        let result = simplifier.simplify_equation(expr).unwrap();
+        let (terms, constant) = result.as_homogeneous();
+
+        assert_eq!(
+            terms,
+            BTreeMap::from([(String::from("x"), 2.0f64), (String::from("y"), -1.0)])
+        );
+        assert_eq!(constant, -3.0);
+    }
+
+    #[test]
+    pub fn test_inequality() {
+        let expr = "2x + 1 < 3 - y";
+        let simplifier = Simplifier::new();
+
+        let result = simplifier.simplify_inequality(expr).unwrap();
+
+        assert_eq!(
+            result.terms,
+            HashMap::from([(String::from("x"), 2.0f64), (String::from("y"), 1.0)])
+        );
+        assert_eq!(result.constant, 2.0);
+        assert_eq!(result.relation, Relation::Lt);
+
+        let result = simplifier.simplify_inequality("x >= 4").unwrap();
+        assert_eq!(result.relation, Relation::Ge);
+    }
+
+    #[test]
+    pub fn test_exact_fractions() {
+        let expr = "(1/3)x + y/4 = 1/6";
+        let simplifier = Simplifier::new();
+
+        let result = simplifier.simplify_equation_exact(expr).unwrap();
+
+        assert_eq!(
+            result.terms,
+            HashMap::from([
+                (
+                    String::from("x"),
+                    Coefficient::Rational(Rational::new(1, 3).unwrap())
+                ),
+                (
+                    String::from("y"),
+                    Coefficient::Rational(Rational::new(1, 4).unwrap())
+                ),
+            ])
+        );
+        assert_eq!(
+            result.constant,
+            Coefficient::Rational(Rational::new(1, 6).unwrap())
+        );
+
+        let result = simplifier
+            .simplify_equation_exact("x\\sqrt(4) = 1")
+            .unwrap();
+        assert_eq!(result.terms["x"], Coefficient::Real(2.0));
+        assert_eq!(result.constant, Coefficient::Real(1.0));
+        assert_eq!(result.to_rational(), None);
+
+        let result = simplifier.simplify_equation_exact("x/3 = 2").unwrap();
+        let exact = result.to_rational().unwrap();
+        assert_eq!(exact.terms["x"], Rational::new(1, 3).unwrap());
+        assert_eq!(exact.constant, Rational::from_integer(2));
+    }
+
+    #[test]
+    pub fn test_exact_extreme_values() {
+        let simplifier = Simplifier::new();
+
+        let result = simplifier
+            .simplify_equation_exact("x = 1^2147483647")
+            .unwrap();
+        assert_eq!(
+            result.constant,
+            Coefficient::Rational(Rational::from_integer(1))
+        );
+
+        // The inner difference is exactly `i64::MIN`, whose negation does not
+        // fit, so the float result is kept.
+        let result = simplifier
+            .simplify_equation_exact("x = -(-4611686018427387904 - 4611686018427387904)")
+            .unwrap();
+        assert_eq!(result.constant, Coefficient::Real(9223372036854775808.0));
+    }
+
+    #[test]
+    pub fn test_simplify_many() {
+        let simplifier = Simplifier::new();
+
+        let results = simplifier.simplify_many(&["x + y = 2", "x * y = 1", "2x = 4"]);
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            results[0].as_ref().unwrap().terms,
+            HashMap::from([(String::from("x"), 1.0f64), (String::from("y"), 1.0)])
+        );
+        assert!(results[1].is_err());
+        assert_eq!(results[2].as_ref().unwrap().constant, 4.0);
+    }
+
+    #[test]
+    pub fn test_evaluator_error_side() {
+        let simplifier = Simplifier::new();
+
+        match simplifier.simplify_equation("2x = x * y") {
+            Err(SimplifierError::EvaluatorError(e)) => {
+                assert_eq!(e.side, Some(EquationSide::Right))
+            }
+            _ => panic!("Expected an evaluator error"),
+        }
+
+        match simplifier.simplify_equation("x / y = 2") {
+            Err(SimplifierError::EvaluatorError(e)) => {
+                assert_eq!(e.side, Some(EquationSide::Left))
+            }
+            _ => panic!("Expected an evaluator error"),
+        }
+    }
+
+    #[test]
+    pub fn test_negate() {
+        let simplifier = Simplifier::new();
+        let original = simplifier.simplify_equation("2x - y = 3").unwrap();
+
+        let mut negated = original.clone();
+        negated.negate();
+
+        assert_eq!(
+            negated.terms,
+            HashMap::from([(String::from("x"), -2.0f64), (String::from("y"), 1.0)])
+        );
+        assert_eq!(negated.constant, -3.0);
+
+        negated.negate();
+        assert_eq!(negated, original);
+    }
+
+    #[test]
+    pub fn test_equation_arithmetic() {
+        let simplifier = Simplifier::new();
+        let first = simplifier.simplify_equation("x + 2y = 7").unwrap();
+        let second = simplifier.simplify_equation("3x - y = 2").unwrap();
+
+        let sum = first.clone() + second.clone();
+        assert_eq!(
+            sum.terms,
+            HashMap::from([(String::from("x"), 4.0f64), (String::from("y"), 1.0)])
+        );
+        assert_eq!(sum.constant, 9.0);
+
+        let mut scaled = second;
+        scaled.scale(2.0);
+        let difference = first.clone() - scaled;
+        assert_eq!(
+            difference.terms,
+            HashMap::from([(String::from("x"), -5.0f64), (String::from("y"), 4.0)])
+        );
+        assert_eq!(difference.constant, 3.0);
+
+        let zero = first.clone() - first.clone();
+        assert!(zero.terms.is_empty());
+        assert_eq!(zero.constant, 0.0);
+        assert!(zero.is_identity());
+
+        // `y` cancels, leaving a single equation in a single variable.
+        let eliminated = first - simplifier.simplify_equation("2y = 4").unwrap();
+        assert_eq!(eliminated.terms, HashMap::from([(String::from("x"), 1.0)]));
+        assert_eq!(
+            crate::Solver::solve(&[eliminated]).unwrap(),
+            HashMap::from([(String::from("x"), 3.0)])
+        );
+    }
+
+    #[test]
+    pub fn test_explain() {
+        let simplifier = Simplifier::new();
+        let (equation, steps) = simplifier.explain("3(x + 2) - y = 2(y - 1)").unwrap();
+
+        assert_eq!(
+            equation,
+            simplifier
+                .simplify_equation("3(x + 2) - y = 2(y - 1)")
+                .unwrap()
+        );
+        assert_eq!(
+            steps,
+            [
+                "Parsed: 3*(x + 2) - y = 2*(y - 1)",
+                "Expanded: 3x + (6) + (-y) = 2y + (-2)",
+                "Combined: 3x + -3y = -8",
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_simplify_reader() {
+        let simplifier = Simplifier::new();
+        let reader = BufReader::new("2x + 3y\n  = 6 - x".as_bytes());
+
+        let result = simplifier.simplify_reader(reader).unwrap();
+        assert_eq!(
+            result,
+            simplifier.simplify_equation("2x + 3y = 6 - x").unwrap()
+        );
+
+        let reader = BufReader::new("x = 1\n  + @".as_bytes());
+        match simplifier.simplify_reader(reader) {
+            Err(SimplifierError::TokenizerError(TokenizerError::UnknownCharacter(
+                b'@',
+                line,
+                column,
+            ))) => {
+                assert_eq!((line, column), (2, 5))
+            }
+            other => panic!("Expected an unknown character error, found {other:?}"),
+        }
+    }
+
+    #[test]
+    pub fn test_numeric_equations() {
+        let simplifier = Simplifier::new();
+
+        let identity = simplifier.simplify_equation("2 + 3 = 5").unwrap();
+        assert!(identity.terms.is_empty());
+        assert_eq!(identity.constant, 0.0);
+        assert!(identity.is_identity());
+
+        let contradiction = simplifier.simplify_equation("2^3 = \\sqrt(4)").unwrap();
+        assert_eq!(contradiction.constant, -6.0);
+        assert!(contradiction.is_contradiction());
+        assert!(!contradiction.is_identity());
+
+        assert!(simplifier.simplify_equation("x = x").unwrap().is_identity());
+        assert!(
+            !simplifier
+                .simplify_equation("x = 1")
+                .unwrap()
+                .is_contradiction()
+        );
+
+        match simplifier.simplify_equation("1 = 2/(1 - 1)") {
+            Err(SimplifierError::EvaluatorError(error)) => {
+                assert_eq!(error.side, Some(EquationSide::Right))
+            }
+            other => panic!("Expected an evaluator error, found {other:?}"),
+        }
+    }
+
+    #[test]
+    pub fn test_evaluate() {
+        let value = evaluate("2(x + 1) - 3").unwrap();
+        assert!(value.approx_eq(
+            &Value::Sum(vec![
+                Value::new_monomial(2.0, String::from("x")),
+                Value::new_constant(-1.0),
+            ]),
+            0.0
+        ));
+        assert_eq!(evaluate("2^3").unwrap(), Value::new_constant(8.0));
+
+        assert!(matches!(
+            evaluate("x = 1"),
+            Err(SimplifierError::LexerError(LexerError::ExpectedEof { .. }))
+        ));
+    }
+
+    #[test]
+    pub fn test_assume_zero_rhs() {
+        assert!(matches!(
+            Simplifier::new().simplify_equation("2x + 3y"),
+            Err(SimplifierError::LexerError(
+                LexerError::ExpectedTokenFoundEof { .. }
+            ))
+        ));
+
+        let simplifier = Simplifier::new().assume_zero_rhs(true);
+        assert_eq!(
+            simplifier.simplify_equation("2x + 3y - 6").unwrap(),
+            simplifier.simplify_equation("2x + 3y = 6").unwrap()
+        );
+        assert_eq!(
+            simplifier.simplify_inequality("x - 1").unwrap().relation,
+            Relation::Eq
+        );
+        assert_eq!(
+            simplifier.simplify_inequality("x < 1").unwrap().relation,
+            Relation::Lt
+        );
+    }
+
+    #[test]
+    pub fn test_from_str() {
+        let equation: CanonicalEquation = "2x + 3 = 7".parse().unwrap();
+        assert_eq!(
+            equation,
+            Simplifier::new().simplify_equation("2x + 3 = 7").unwrap()
+        );
+
+        let system: Result<Vec<CanonicalEquation>, _> = ["x + y = 2", "x - y = 0"]
+            .into_iter()
+            .map(str::parse)
+            .collect();
+        assert_eq!(system.unwrap().len(), 2);
+
+        let invalid: Result<Vec<CanonicalEquation>, _> =
+            ["x = 1", "x +"].into_iter().map(str::parse).collect();
+        assert!(matches!(invalid, Err(SimplifierError::LexerError(_))));
+    }
+
+    #[test]
+    pub fn test_solve_for() {
+        let simplifier = Simplifier::new();
+        let equation = simplifier.simplify_equation("2x + y = 5").unwrap();
+        let known = HashMap::from([(String::from("y"), 1.0)]);
+
+        assert_eq!(equation.solve_for("x", &known), Some(2.0));
+        assert_eq!(equation.solve_for("y", &HashMap::new()), None);
+        assert_eq!(equation.solve_for("z", &known), None);
+    }
+
+    #[test]
+    pub fn test_canonical_key() {
+        let simplifier = Simplifier::new();
+        let first = simplifier.simplify_equation("2x + 4 = 0").unwrap();
+        let second = simplifier.simplify_equation("-x - 2 = 0").unwrap();
+
+        assert_eq!(first.canonical_key(), "1x = -2");
+        assert_eq!(first.canonical_key(), second.canonical_key());
+
+        let first = simplifier.simplify_equation("6y - 3x = 9 + 0z").unwrap();
+        let second = simplifier.simplify_equation("x - 2y + 3 = 0").unwrap();
+        assert_eq!(first.canonical_key(), "1x + -2y = -3");
+        assert_eq!(first.canonical_key(), second.canonical_key());
+
+        let fractional = simplifier.simplify_equation("0.5x = 1").unwrap();
+        assert_eq!(fractional.canonical_key(), "0.5x = 1");
+    }
+
+    #[test]
+    pub fn test_scale() {
+        let simplifier = Simplifier::new();
+        let mut equation = simplifier.simplify_equation("3x - 6y = 9").unwrap();
+
+        equation.scale(1.0 / 3.0);
+        assert_eq!(
+            equation.terms,
+            HashMap::from([(String::from("x"), 1.0f64), (String::from("y"), -2.0)])
+        );
+        assert_eq!(equation.constant, 3.0);
+
+        equation.scale(0.0);
+        assert!(equation.terms.is_empty());
+        assert_eq!(equation.constant, 0.0);
+    }
+
+    #[test]
+    pub fn test_simplify_to_value() {
+        let value = Simplifier::new()
+            .simplify_to_value("3(x + 1) = y - 2")
+            .unwrap();
+
+        assert_eq!(
+            value,
+            Value::Sum(vec![
+                Value::new_monomial(3.0, String::from("x")),
+                Value::new_constant(5.0),
+                Value::new_monomial(-1.0, String::from("y")),
+            ])
+        );
+    }
+
+    #[test]
+    pub fn test_constant_stays_on_rhs() {
+        let equation = Simplifier::new().simplify_equation("3x + 2y = 7").unwrap();
+        assert_eq!(equation.constant, 7.0);
+        assert_eq!(equation.rhs(), 7.0);
+        assert_eq!(equation.as_homogeneous().1, -7.0);
+
+        let exact = Simplifier::new()
+            .simplify_equation_exact("x = 1/3")
+            .unwrap();
+        assert_eq!(
+            exact.constant,
+            Coefficient::Rational(Rational::new(1, 3).unwrap())
+        );
+    }
+
+    #[test]
+    pub fn test_case_insensitive_variables() {
+        let equation = Simplifier::new().simplify_equation("X + x = 2").unwrap();
+        assert_eq!(
+            equation.terms,
+            HashMap::from([(String::from("X"), 1.0), (String::from("x"), 1.0)])
+        );
+
+        let simplifier = Simplifier::new().case_insensitive_variables(true);
+        let equation = simplifier.simplify_equation("X + x = 2").unwrap();
+        assert_eq!(equation.terms, HashMap::from([(String::from("x"), 2.0)]));
+        assert_eq!(equation.constant, 2.0);
+
+        let equation = simplifier.simplify_equation_exact("X/3 + x = 2").unwrap();
+        assert_eq!(
+            equation.terms,
+            HashMap::from([(
+                String::from("x"),
+                Coefficient::Rational(Rational::new(4, 3).unwrap())
+            )])
+        );
+    }
+
+    #[test]
+    pub fn test_simplify_equation_into() {
+        let simplifier = Simplifier::new();
+        let mut terms = HashMap::new();
+
+        for input in ["2x + 3y = 7", "z - 1 = 2(x + 1)", "4 = 2 + 2", "y = 3"] {
+            let constant = simplifier
+                .simplify_equation_into(input, &mut terms)
+                .unwrap();
+            let fresh = simplifier.simplify_equation(input).unwrap();
+
+            assert_eq!(terms, fresh.terms);
+            assert_eq!(constant, fresh.constant);
+        }
+    }
+
+    #[test]
+    pub fn test_textbook_sides() {
+        let equation = Simplifier::new().simplify_equation("2y + 3x = 7").unwrap();
+        assert_eq!(equation.lhs_terms(), [("x", 3.0), ("y", 2.0)]);
+        assert_eq!(equation.rhs(), 7.0);
+
+        let equation = Simplifier::new().simplify_equation("x - 7 = 0").unwrap();
+        assert_eq!(equation.lhs_terms(), [("x", 1.0)]);
+        assert_eq!(equation.rhs(), 7.0);
+    }
+
+    #[test]
+    pub fn test_simplify_equation_checked() {
+        let simplifier = Simplifier::new();
+
+        let (_, warnings) = simplifier.simplify_equation_checked("2x + y = 3").unwrap();
+        assert!(warnings.is_empty());
+
+        let (equation, warnings) = simplifier
+            .simplify_equation_checked("1000000000000000000x + y/10000000000000 = 1")
+            .unwrap();
+        assert_eq!(equation.terms.len(), 2);
+        assert_eq!(
+            warnings,
+            [
+                Warning::LargeCoefficient {
+                    variable: Some(String::from("x")),
+                    value: 1e18
+                },
+                Warning::SmallCoefficient {
+                    variable: Some(String::from("y")),
+                    value: 1e-13
+                },
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_is_equivalent() {
+        let equation = |text: &str| {
+            let tokens = Simplifier::new().tokenize(text).unwrap();
+            Lexer::new(tokens).relation().unwrap()
+        };
+
+        assert!(equation("2x + 4 = 0").is_equivalent(&equation("x = -2")));
+        assert!(equation("x + y = 1").is_equivalent(&equation("3 - 3y = 3x")));
+        assert!(equation("x/3 = y").is_equivalent(&equation("x - 3y = 0")));
+        assert!(!equation("2x + 4 = 0").is_equivalent(&equation("x = 2")));
+        assert!(!equation("x + y = 1").is_equivalent(&equation("x = 1")));
+        assert!(!equation("x < 1").is_equivalent(&equation("x < 1")));
+    }
+
+    #[test]
+    pub fn test_parse_and_simplify() {
+        let simplifier = Simplifier::new();
+        let (equation, canonical) = simplifier.parse_and_simplify("2(x + 1) = y").unwrap();
+
+        assert_eq!(equation.left.to_infix(), "2*(x + 1)");
+        assert_eq!(equation.right.to_infix(), "y");
+        assert_eq!(
+            canonical,
+            simplifier.simplify_equation("2(x + 1) = y").unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_round_to() {
+        let mut equation = Simplifier::new()
+            .simplify_equation("x/3 + 0.1y = 0.2 + 0.1")
+            .unwrap();
+        equation.round_to(4);
+
+        assert_eq!(
+            equation.terms,
+            HashMap::from([(String::from("x"), 0.3333), (String::from("y"), 0.1)])
+        );
+        assert_eq!(equation.constant, 0.3);
+    }
+
+    #[test]
+    pub fn test_substitute() {
+        let mut equation = Simplifier::new().simplify_equation("x + 3y = 10").unwrap();
+        equation.substitute("y", 2.0);
+        assert_eq!(equation, "x = 4".parse().unwrap());
+
+        equation.substitute("z", 5.0);
+        assert_eq!(equation, "x - 4 = 0".parse().unwrap());
+
+        equation.substitute("x", 4.0);
+        assert!(equation.is_identity());
+    }
+
+    #[test]
+    pub fn test_to_parts() {
+        let equation = Simplifier::new()
+            .simplify_equation("z + 3b - y + 2a = 7")
+            .unwrap();
+        let (terms, constant) = equation.to_parts();
+
+        assert_eq!(
+            terms.into_iter().collect::<Vec<_>>(),
+            [
+                (String::from("a"), 2.0),
+                (String::from("b"), 3.0),
+                (String::from("y"), -1.0),
+                (String::from("z"), 1.0),
+            ]
+        );
+        assert_eq!(constant, 7.0);
+    }
+
+    #[test]
+    pub fn test_dense_terms() {
+        let equation = Simplifier::new().simplify_equation("2x - z = 4").unwrap();
+        let variables = [String::from("z"), String::from("y"), String::from("x")];
+
+        assert_eq!(equation.dense_terms(&variables), [-1.0, 0.0, 2.0]);
+        assert!(equation.dense_terms(&[]).is_empty());
+    }
+
+    #[test]
+    pub fn test_to_string_scientific() {
+        let equation = Simplifier::new()
+            .simplify_equation("0.0000123x - 45600y = 2")
+            .unwrap();
+
+        assert_eq!(
+            equation.to_string_scientific(3),
+            "1.23e-5x + -4.56e4y = 2.00e0"
+        );
+        assert_eq!(equation.to_string_scientific(0), "1e-5x + -5e4y = 2e0");
+    }
+
+    #[test]
+    pub fn test_output_formats() {
+        let equation = Simplifier::new()
+            .simplify_equation("2x_1 + 4 = y - 1 + 0z")
+            .unwrap();
+
+        assert_eq!(equation.to_sexpr(), "(= (+ (* 2 x_1) (* -1 y)) -5)");
+        assert_eq!(equation.to_infix(), "2x_1 - y = -5");
+        assert_eq!(equation.to_latex(), "2x_{1} - y = -5");
+        assert_eq!(
+            equation.to_json(),
+            "{\"terms\":{\"x_1\":2,\"y\":-1},\"constant\":-5}"
+        );
+
+        let equation = Simplifier::new()
+            .simplify_equation("-x + 0.5big_ = 3")
+            .unwrap();
+        assert_eq!(equation.to_sexpr(), "(= (+ (* 0.5 big_) (* -1 x)) 3)");
+        assert_eq!(equation.to_infix(), "0.5big_ - x = 3");
+        assert_eq!(equation.to_latex(), "0.5big\\_ - x = 3");
+        assert_eq!(
+            equation.to_json(),
+            "{\"terms\":{\"big_\":0.5,\"x\":-1},\"constant\":3}"
+        );
+
+        let equation = Simplifier::new().simplify_equation("x - x = 0").unwrap();
+        assert_eq!(equation.to_sexpr(), "(= 0 0)");
+        assert_eq!(equation.to_infix(), "0 = 0");
+        assert_eq!(equation.to_latex(), "0 = 0");
+        assert_eq!(equation.to_json(), "{\"terms\":{},\"constant\":0}");
+    }
+
+    #[test]
+    pub fn test_error_codes() {
+        let simplifier = Simplifier::new();
+        let code = |input: &str| simplifier.simplify_equation(input).unwrap_err().code();
+
+        assert_eq!(code("x @ 1"), "unknown_character");
+        assert_eq!(code("(x + 1 = 2"), "unmatched_paren");
+        assert_eq!(code("x * y = 1"), "variable_multiplication");
+    }
+
+    #[test]
+    pub fn test_canonicalize_sides() {
+        let (left, right) = Simplifier::new()
+            .canonicalize_sides("2x + 3x = y + 2 + y")
+            .unwrap();
+
+        assert_eq!(left.terms, HashMap::from([(String::from("x"), 5.0)]));
+        assert_eq!(left.constant, 0.0);
+        assert_eq!(right.terms, HashMap::from([(String::from("y"), 2.0)]));
+        assert_eq!(right.constant, -2.0);
+    }
+
+    #[test]
+    pub fn test_error_location() {
+        let simplifier = Simplifier::new();
+        let location = |input: &str| simplifier.simplify_equation(input).unwrap_err().location();
+
+        assert_eq!(location("2x @ 1"), Some((1, 4)));
+        assert_eq!(location("x + (y = 1"), Some((1, 5)));
+        assert_eq!(location("x = 2 / 0"), Some((1, 9)));
+        assert_eq!(location("x + 1"), None);
+
+        assert_eq!(location("x = 1\n  + @"), Some((2, 5)));
+        assert_eq!(location("x +\r\n(y\n= 1"), Some((2, 1)));
+        assert_eq!(location("x =\r1 +\r\n2 / 0"), Some((3, 5)));
+        assert_eq!(location("x = 1\n+ 2\n\t* *"), Some((3, 4)));
+    }
+
+    #[test]
+    pub fn test_diff() {
+        let answer: CanonicalEquation = "2x + 3 = 0".parse().unwrap();
+        let expected: CanonicalEquation = "2x + 5 = 0".parse().unwrap();
+        assert_eq!(answer.diff(&expected), [(String::new(), 2.0)]);
+
+        let answer: CanonicalEquation = "x + 2y = 1".parse().unwrap();
+        let expected: CanonicalEquation = "x + z = 1".parse().unwrap();
+        assert_eq!(
+            answer.diff(&expected),
+            [(String::from("y"), 2.0), (String::from("z"), -1.0)]
+        );
+        assert!(answer.diff(&answer).is_empty());
+    }
 }
@@ -3,12 +3,36 @@ use std::fmt::{Display, Formatter};
 pub type SimplifierResult<T> = Result<T, SimplifierError>;
 
 #[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum SimplifierError {
     LexerError(crate::lexer::LexerError),
     TokenizerError(crate::tokenizer::TokenizerError),
     EvaluatorError(crate::evaluator::EvaluatorError),
 }
 
+impl SimplifierError {
+    /// The stable code of the underlying error, see [`LexerError::code`](crate::LexerError::code).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::LexerError(err) => err.code(),
+            Self::TokenizerError(err) => err.code(),
+            Self::EvaluatorError(err) => err.code(),
+        }
+    }
+}
+
+impl SimplifierError {
+    /// Where the error was found as `(line, column)`, whichever stage produced
+    /// it, or `None` if the error does not point at a position.
+    pub fn location(&self) -> Option<(usize, usize)> {
+        match self {
+            Self::TokenizerError(err) => Some((err.line(), err.column())),
+            Self::LexerError(err) => Some((err.line()?, err.column()?)),
+            Self::EvaluatorError(err) => Some((err.token.line, err.token.column)),
+        }
+    }
+}
+
 impl From<crate::tokenizer::TokenizerError> for SimplifierError {
     fn from(err: crate::tokenizer::TokenizerError) -> Self {
         Self::TokenizerError(err)
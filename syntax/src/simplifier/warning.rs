@@ -0,0 +1,61 @@
+use std::fmt::{Display, Formatter};
+
+/// Coefficients with a larger magnitude than this are likely to lose precision
+/// once combined with others.
+pub(crate) const LARGE_COEFFICIENT: f64 = 1e15;
+
+/// Non-zero coefficients with a smaller magnitude than this are likely rounding
+/// noise rather than intended values.
+pub(crate) const SMALL_COEFFICIENT: f64 = 1e-12;
+
+/// A non-fatal issue found while simplifying, see
+/// [`Simplifier::simplify_equation_checked`](super::Simplifier::simplify_equation_checked).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Warning {
+    /// The coefficient of `variable`, or the constant when it is `None`, is huge.
+    LargeCoefficient {
+        variable: Option<String>,
+        value: f64,
+    },
+    /// The coefficient of `variable`, or the constant when it is `None`, is tiny
+    /// but not zero.
+    SmallCoefficient {
+        variable: Option<String>,
+        value: f64,
+    },
+}
+
+impl Warning {
+    /// Checks a single coefficient against both thresholds.
+    pub(crate) fn check(variable: Option<&str>, value: f64) -> Option<Self> {
+        let variable = variable.map(String::from);
+
+        if value.abs() > LARGE_COEFFICIENT {
+            Some(Self::LargeCoefficient { variable, value })
+        } else if value != 0.0 && value.abs() < SMALL_COEFFICIENT {
+            Some(Self::SmallCoefficient { variable, value })
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (variable, value, size) = match self {
+            Self::LargeCoefficient { variable, value } => (variable, value, "large"),
+            Self::SmallCoefficient { variable, value } => (variable, value, "small"),
+        };
+
+        match variable {
+            Some(variable) => write!(
+                f,
+                "The coefficient {value} of {variable} is very {size} and may be imprecise"
+            ),
+            None => write!(
+                f,
+                "The constant {value} is very {size} and may be imprecise"
+            ),
+        }
+    }
+}
@@ -0,0 +1,611 @@
+mod error;
+mod lu;
+
+use crate::{CanonicalEquation, Rational};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+pub use error::{SolverError, SolverResult};
+pub use lu::LuFactorization;
+
+/// Coefficients whose magnitude is below this value are treated as zero during
+/// elimination.
+const PIVOT_EPSILON: f64 = 1e-10;
+
+/// Solves systems of canonical equations through Gauss-Jordan elimination.
+pub struct Solver;
+
+/// How many solutions a system of equations has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemClass {
+    Unique,
+    Infinite,
+    Inconsistent,
+}
+
+/// The dimensions of a system of equations, see [`Solver::analyze`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemInfo {
+    /// Every variable in the system, sorted by name.
+    pub variables: Vec<String>,
+    pub equation_count: usize,
+    /// The rank of the coefficient matrix, up to the elimination tolerance.
+    pub rank_hint: usize,
+}
+
+impl SystemInfo {
+    /// Whether there are as many independent equations as unknowns, which is
+    /// needed, but not enough, for a unique solution.
+    pub fn looks_solvable(&self) -> bool {
+        self.rank_hint == self.variables.len()
+    }
+}
+
+impl Solver {
+    pub fn solve(equations: &[CanonicalEquation]) -> SolverResult<HashMap<String, f64>> {
+        Ok(Self::solve_ordered(equations)?.into_iter().collect())
+    }
+
+    /// Solves the system, returning the value of each variable sorted by the
+    /// variable's name.
+    pub fn solve_ordered(equations: &[CanonicalEquation]) -> SolverResult<Vec<(String, f64)>> {
+        if equations.is_empty() {
+            return Err(SolverError::EmptySystem);
+        }
+
+        let variables = Self::variables(equations);
+        let mut matrix = Self::augmented_matrix(equations, &variables);
+        let pivots = Self::row_reduce(&mut matrix, variables.len());
+
+        if Self::is_inconsistent(&matrix, variables.len()) {
+            return Err(SolverError::Inconsistent);
+        }
+
+        if pivots.len() < variables.len() {
+            return Err(SolverError::Underdetermined {
+                rank: pivots.len(),
+                variables: variables.len(),
+            });
+        }
+
+        Ok(pivots
+            .iter()
+            .enumerate()
+            .map(|(row, column)| (variables[*column].clone(), matrix[row][variables.len()]))
+            .collect())
+    }
+
+    /// Solves the system, returning the value of each variable in `order`. Fails
+    /// with [`SolverError::UnknownVariable`] if `order` names a variable the
+    /// system does not have; variables left out of `order` are omitted.
+    pub fn solve_with_order(
+        equations: &[CanonicalEquation],
+        order: &[String],
+    ) -> SolverResult<Vec<f64>> {
+        let solution = Self::solve(equations)?;
+
+        order
+            .iter()
+            .map(|variable| {
+                solution
+                    .get(variable)
+                    .copied()
+                    .ok_or_else(|| SolverError::UnknownVariable(variable.clone()))
+            })
+            .collect()
+    }
+
+    /// Factors the coefficients of a square system once, so that it can be solved
+    /// for many different constants with [`LuFactorization::solve_rhs`]. Fails
+    /// with [`SolverError::NotSquare`] unless there are as many equations as
+    /// variables, and with [`SolverError::Underdetermined`] if the coefficients
+    /// are singular.
+    pub fn factor(equations: &[CanonicalEquation]) -> SolverResult<LuFactorization> {
+        if equations.is_empty() {
+            return Err(SolverError::EmptySystem);
+        }
+
+        let variables = Self::variables(equations);
+        if equations.len() != variables.len() {
+            return Err(SolverError::NotSquare {
+                equations: equations.len(),
+                variables: variables.len(),
+            });
+        }
+
+        let coefficients: Vec<Vec<f64>> = equations
+            .iter()
+            .map(|equation| equation.dense_terms(&variables))
+            .collect();
+
+        match LuFactorization::new(variables.clone(), coefficients.clone()) {
+            Some(factorization) => Ok(factorization),
+            None => {
+                let mut matrix = coefficients;
+                Err(SolverError::Underdetermined {
+                    rank: Self::row_reduce(&mut matrix, variables.len()).len(),
+                    variables: variables.len(),
+                })
+            }
+        }
+    }
+
+    /// Whether every value of the solution is within `epsilon` of an integer.
+    pub fn is_integer_solution(solution: &HashMap<String, f64>, epsilon: f64) -> bool {
+        solution
+            .values()
+            .all(|value| (value - value.round()).abs() <= epsilon)
+    }
+
+    /// Solves the system like [`Solver::solve`], but eliminating with exact
+    /// fractions, so `3x = 1` gives `1/3` with no rounding error.
+    pub fn solve_exact(
+        equations: &[CanonicalEquation<Rational>],
+    ) -> SolverResult<HashMap<String, Rational>> {
+        if equations.is_empty() {
+            return Err(SolverError::EmptySystem);
+        }
+
+        let variables = Self::variables(equations);
+        let columns = variables.len();
+        let mut matrix: Vec<Vec<Rational>> = equations
+            .iter()
+            .map(|equation| {
+                let mut row: Vec<Rational> = variables
+                    .iter()
+                    .map(|variable| {
+                        let coefficient = equation.terms.get(variable).copied();
+                        coefficient.unwrap_or(Rational::from_integer(0))
+                    })
+                    .collect();
+                row.push(equation.constant);
+                row
+            })
+            .collect();
+
+        let pivots = Self::row_reduce_exact(&mut matrix, columns).ok_or(SolverError::Overflow)?;
+
+        let inconsistent = matrix
+            .iter()
+            .any(|row| row[..columns].iter().all(Rational::is_zero) && !row[columns].is_zero());
+        if inconsistent {
+            return Err(SolverError::Inconsistent);
+        }
+
+        if pivots.len() < columns {
+            return Err(SolverError::Underdetermined {
+                rank: pivots.len(),
+                variables: columns,
+            });
+        }
+
+        Ok(pivots
+            .iter()
+            .enumerate()
+            .map(|(row, column)| (variables[*column].clone(), matrix[row][columns]))
+            .collect())
+    }
+
+    /// Summarizes the size of the system before solving it. Unlike
+    /// [`Solver::solve`], an empty system is not an error.
+    pub fn analyze(equations: &[CanonicalEquation]) -> SystemInfo {
+        let variables = Self::variables(equations);
+        let mut matrix = Self::augmented_matrix(equations, &variables);
+        let rank_hint = Self::row_reduce(&mut matrix, variables.len()).len();
+
+        SystemInfo {
+            variables,
+            equation_count: equations.len(),
+            rank_hint,
+        }
+    }
+
+    /// Tells whether the system has a unique solution, infinitely many or none,
+    /// without extracting the solution.
+    ///
+    /// A [homogeneous](Solver::is_homogeneous) system is never inconsistent, since
+    /// setting every variable to zero solves it. It is [`SystemClass::Unique`] when
+    /// that trivial solution is the only one, and [`SystemClass::Infinite`] when
+    /// the rank is short and there are nontrivial solutions as well.
+    pub fn classify(equations: &[CanonicalEquation]) -> SolverResult<SystemClass> {
+        if equations.is_empty() {
+            return Err(SolverError::EmptySystem);
+        }
+
+        let variables = Self::variables(equations);
+        let mut matrix = Self::augmented_matrix(equations, &variables);
+        let pivots = Self::row_reduce(&mut matrix, variables.len());
+
+        Ok(if Self::is_inconsistent(&matrix, variables.len()) {
+            SystemClass::Inconsistent
+        } else if pivots.len() < variables.len() {
+            SystemClass::Infinite
+        } else {
+            SystemClass::Unique
+        })
+    }
+
+    /// Whether every constant is zero, up to the elimination tolerance, so the
+    /// system is solved by setting every variable to zero.
+    pub fn is_homogeneous(equations: &[CanonicalEquation]) -> bool {
+        equations
+            .iter()
+            .all(|equation| equation.constant.abs() < PIVOT_EPSILON)
+    }
+
+    /// Drops every equation that is a multiple of an earlier one, keeping the
+    /// first of each. Equations are compared through
+    /// [`CanonicalEquation::canonical_key`], so only multiples by an integer factor
+    /// of integral equations, or by `-1`, are detected.
+    pub fn remove_redundant(equations: &[CanonicalEquation]) -> Vec<CanonicalEquation> {
+        let mut keys = HashSet::new();
+
+        equations
+            .iter()
+            .filter(|equation| keys.insert(equation.canonical_key()))
+            .cloned()
+            .collect()
+    }
+
+    /// Joins both systems, `a` first, dropping redundant equations like
+    /// [`Solver::remove_redundant`] does.
+    pub fn merge(a: &[CanonicalEquation], b: &[CanonicalEquation]) -> Vec<CanonicalEquation> {
+        let combined: Vec<CanonicalEquation> = a.iter().chain(b).cloned().collect();
+        Self::remove_redundant(&combined)
+    }
+
+    fn variables<C>(equations: &[CanonicalEquation<C>]) -> Vec<String> {
+        let variables: BTreeSet<&String> = equations
+            .iter()
+            .flat_map(|equation| equation.terms.keys())
+            .collect();
+
+        variables.into_iter().cloned().collect()
+    }
+
+    fn augmented_matrix(equations: &[CanonicalEquation], variables: &[String]) -> Vec<Vec<f64>> {
+        equations
+            .iter()
+            .map(|equation| {
+                let mut row = equation.dense_terms(variables);
+                row.push(equation.constant);
+                row
+            })
+            .collect()
+    }
+
+    /// Reduces the augmented matrix to its reduced row echelon form in place,
+    /// returning the column of the pivot of each non-zero row.
+    fn row_reduce(matrix: &mut [Vec<f64>], columns: usize) -> Vec<usize> {
+        let mut pivots = Vec::new();
+        let mut row = 0;
+
+        for column in 0..columns {
+            if row >= matrix.len() {
+                break;
+            }
+
+            let pivot_row = (row..matrix.len())
+                .max_by(|a, b| {
+                    matrix[*a][column]
+                        .abs()
+                        .total_cmp(&matrix[*b][column].abs())
+                })
+                .unwrap();
+
+            if matrix[pivot_row][column].abs() < PIVOT_EPSILON {
+                continue;
+            }
+
+            matrix.swap(row, pivot_row);
+
+            let pivot = matrix[row][column];
+            for value in matrix[row].iter_mut() {
+                *value /= pivot;
+            }
+
+            let pivot_values = matrix[row].clone();
+            for (index, other) in matrix.iter_mut().enumerate() {
+                let factor = other[column];
+                if index == row || factor == 0.0 {
+                    continue;
+                }
+
+                for (value, pivot_value) in other.iter_mut().zip(&pivot_values).skip(column) {
+                    *value -= factor * pivot_value;
+                }
+            }
+
+            pivots.push(column);
+            row += 1;
+        }
+
+        pivots
+    }
+
+    /// Same as [`Solver::row_reduce`] over fractions. Any non-zero pivot is exact,
+    /// so the first one found is used. Returns `None` on overflow.
+    fn row_reduce_exact(matrix: &mut [Vec<Rational>], columns: usize) -> Option<Vec<usize>> {
+        let mut pivots = Vec::new();
+        let mut row = 0;
+
+        for column in 0..columns {
+            if row >= matrix.len() {
+                break;
+            }
+
+            let Some(pivot_row) = (row..matrix.len()).find(|r| !matrix[*r][column].is_zero())
+            else {
+                continue;
+            };
+            matrix.swap(row, pivot_row);
+
+            let pivot = matrix[row][column];
+            for value in matrix[row].iter_mut() {
+                *value = value.checked_div(pivot)?;
+            }
+
+            let pivot_values = matrix[row].clone();
+            for (index, other) in matrix.iter_mut().enumerate() {
+                let factor = other[column];
+                if index == row || factor.is_zero() {
+                    continue;
+                }
+
+                for (value, pivot_value) in other.iter_mut().zip(&pivot_values).skip(column) {
+                    *value = value.checked_sub(factor.checked_mul(*pivot_value)?)?;
+                }
+            }
+
+            pivots.push(column);
+            row += 1;
+        }
+
+        Some(pivots)
+    }
+
+    fn is_inconsistent(matrix: &[Vec<f64>], columns: usize) -> bool {
+        matrix.iter().any(|row| {
+            row[..columns].iter().all(|c| c.abs() < PIVOT_EPSILON)
+                && row[columns].abs() >= PIVOT_EPSILON
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solver::{Solver, SolverError, SystemClass, SystemInfo};
+    use crate::{CanonicalEquation, Rational, Simplifier};
+    use std::collections::HashMap;
+
+    #[cfg(test)]
+    fn system_from_text(equations: &[&str]) -> Vec<CanonicalEquation> {
+        let simplifier = Simplifier::new();
+
+        equations
+            .iter()
+            .map(|equation| simplifier.simplify_equation(equation).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_solve() {
+        let system = system_from_text(&["2x + y = 5", "x - y = 1"]);
+        let solution = Solver::solve(&system).unwrap();
+
+        assert_eq!(
+            solution,
+            HashMap::from([(String::from("x"), 2.0), (String::from("y"), 1.0)])
+        );
+    }
+
+    #[test]
+    fn test_solve_ordered() {
+        let system = system_from_text(&["z + b = 3", "a = 1", "b - a = 1"]);
+        let solution = Solver::solve_ordered(&system).unwrap();
+
+        assert_eq!(
+            solution,
+            vec![
+                (String::from("a"), 1.0),
+                (String::from("b"), 2.0),
+                (String::from("z"), 1.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inconsistent_and_underdetermined() {
+        let system = system_from_text(&["x + y = 2", "x + y = 3"]);
+        assert_eq!(Solver::solve(&system), Err(SolverError::Inconsistent));
+
+        let system = system_from_text(&["x + y = 2", "2x + 2y = 4"]);
+        assert_eq!(
+            Solver::solve(&system),
+            Err(SolverError::Underdetermined {
+                rank: 1,
+                variables: 2
+            })
+        );
+
+        assert_eq!(Solver::solve(&[]), Err(SolverError::EmptySystem));
+    }
+
+    #[test]
+    fn test_remove_redundant() {
+        let system = system_from_text(&["x + y = 2", "2x + 2y = 4", "-x - y = -2", "x - y = 0"]);
+        let reduced = Solver::remove_redundant(&system);
+
+        assert_eq!(reduced, [system[0].clone(), system[3].clone()]);
+        assert_eq!(Solver::classify(&reduced), Ok(SystemClass::Unique));
+
+        let reduced = Solver::remove_redundant(&system[..2]);
+        assert_eq!(reduced.len(), 1);
+        assert_eq!(Solver::classify(&reduced), Ok(SystemClass::Infinite));
+    }
+
+    #[test]
+    fn test_classify() {
+        let system = system_from_text(&["x + y = 2", "x + y = 3"]);
+        assert_eq!(Solver::classify(&system), Ok(SystemClass::Inconsistent));
+
+        let system = system_from_text(&["x + y = 2", "x - y = 0"]);
+        assert_eq!(Solver::classify(&system), Ok(SystemClass::Unique));
+
+        assert_eq!(Solver::classify(&[]), Err(SolverError::EmptySystem));
+    }
+
+    #[test]
+    fn test_homogeneous() {
+        let trivial = system_from_text(&["x + y = 0", "x - y = 0"]);
+        assert!(Solver::is_homogeneous(&trivial));
+        assert_eq!(Solver::classify(&trivial), Ok(SystemClass::Unique));
+        assert_eq!(
+            Solver::solve(&trivial),
+            Ok(HashMap::from([
+                (String::from("x"), 0.0),
+                (String::from("y"), 0.0)
+            ]))
+        );
+
+        let nontrivial = system_from_text(&["x + 2y = 0", "3x + 6y = 0"]);
+        assert!(Solver::is_homogeneous(&nontrivial));
+        assert_eq!(Solver::classify(&nontrivial), Ok(SystemClass::Infinite));
+
+        assert!(!Solver::is_homogeneous(&system_from_text(&[
+            "x + y = 0",
+            "x = 1"
+        ])));
+    }
+
+    #[test]
+    fn test_solve_exact() {
+        let simplifier = Simplifier::new();
+        let system: Vec<CanonicalEquation<Rational>> = ["3x = 1", "x + y = 1"]
+            .iter()
+            .map(|text| {
+                let equation = simplifier.simplify_equation_exact(text).unwrap();
+                equation.to_rational().unwrap()
+            })
+            .collect();
+
+        assert_eq!(
+            Solver::solve_exact(&system),
+            Ok(HashMap::from([
+                (String::from("x"), Rational::new(1, 3).unwrap()),
+                (String::from("y"), Rational::new(2, 3).unwrap()),
+            ]))
+        );
+        assert_eq!(
+            Solver::solve_exact(&system[..1]),
+            Ok(HashMap::from([(
+                String::from("x"),
+                Rational::new(1, 3).unwrap()
+            )]))
+        );
+        assert_eq!(Solver::solve_exact(&[]), Err(SolverError::EmptySystem));
+    }
+
+    #[test]
+    fn test_analyze() {
+        let system = system_from_text(&["x + y + z = 6", "2y - z = 1", "x - z = 0"]);
+        let info = Solver::analyze(&system);
+
+        assert_eq!(
+            info,
+            SystemInfo {
+                variables: vec![String::from("x"), String::from("y"), String::from("z")],
+                equation_count: 3,
+                rank_hint: 3,
+            }
+        );
+        assert!(info.looks_solvable());
+
+        let system = system_from_text(&["x + y + z = 6", "2x + 2y + 2z = 12", "x - z = 0"]);
+        let info = Solver::analyze(&system);
+        assert_eq!(info.rank_hint, 2);
+        assert!(!info.looks_solvable());
+
+        assert_eq!(Solver::analyze(&[]).equation_count, 0);
+    }
+
+    #[test]
+    fn test_solve_with_order() {
+        let system = system_from_text(&["a + b + c = 6", "b = 2", "c - a = 2"]);
+        let order = [String::from("c"), String::from("a"), String::from("b")];
+
+        assert_eq!(
+            Solver::solve_with_order(&system, &order),
+            Ok(vec![3.0, 1.0, 2.0])
+        );
+        assert_eq!(
+            Solver::solve_with_order(&system, &[String::from("b")]),
+            Ok(vec![2.0])
+        );
+        assert_eq!(
+            Solver::solve_with_order(&system, &[String::from("a"), String::from("d")]),
+            Err(SolverError::UnknownVariable(String::from("d")))
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let a = system_from_text(&["x + y = 2", "x - y = 0"]);
+        let b = system_from_text(&["2x + 2y = 4", "y - x = 0", "x + z = 1"]);
+        let merged = Solver::merge(&a, &b);
+
+        assert_eq!(merged, [a[0].clone(), a[1].clone(), b[2].clone()]);
+        assert_eq!(Solver::merge(&a, &[]), a);
+    }
+
+    #[test]
+    fn test_is_integer_solution() {
+        let system = system_from_text(&["x + y = 5", "x - y = -1"]);
+        let solution = Solver::solve(&system).unwrap();
+        assert_eq!(
+            solution,
+            HashMap::from([(String::from("x"), 2.0), (String::from("y"), 3.0)])
+        );
+        assert!(Solver::is_integer_solution(&solution, 1e-9));
+
+        let solution = Solver::solve(&system_from_text(&["2x = 1"])).unwrap();
+        assert!(!Solver::is_integer_solution(&solution, 1e-9));
+        assert!(Solver::is_integer_solution(&solution, 0.5));
+    }
+
+    #[test]
+    fn test_factor() {
+        let first =
+            system_from_text(&["2x + y - z = 8", "-3x - y + 2z = -11", "-2x + y + 2z = -3"]);
+        let second = system_from_text(&["2x + y - z = 1", "-3x - y + 2z = 0", "-2x + y + 2z = 4"]);
+        let factorization = Solver::factor(&first).unwrap();
+        let constants = |system: &[CanonicalEquation]| -> Vec<f64> {
+            system.iter().map(|equation| equation.constant).collect()
+        };
+
+        for system in [&first, &second] {
+            let expected = Solver::solve_with_order(system, factorization.variables()).unwrap();
+            let values = factorization.solve_rhs(&constants(system));
+
+            assert_eq!(values.len(), expected.len());
+            for (value, expected) in values.iter().zip(&expected) {
+                assert!((value - expected).abs() < 1e-9);
+            }
+        }
+
+        assert_eq!(
+            Solver::factor(&system_from_text(&["x + y = 2", "2x + 2y = 4"])),
+            Err(SolverError::Underdetermined {
+                rank: 1,
+                variables: 2
+            })
+        );
+        assert_eq!(
+            Solver::factor(&system_from_text(&["x + y = 2"])),
+            Err(SolverError::NotSquare {
+                equations: 1,
+                variables: 2
+            })
+        );
+        assert_eq!(Solver::factor(&[]), Err(SolverError::EmptySystem));
+    }
+}
@@ -0,0 +1,51 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+pub type SolverResult<T> = Result<T, SolverError>;
+
+#[derive(Debug, PartialEq)]
+pub enum SolverError {
+    EmptySystem,
+    Inconsistent,
+    Underdetermined {
+        rank: usize,
+        variables: usize,
+    },
+    /// The system does not have as many equations as variables.
+    NotSquare {
+        equations: usize,
+        variables: usize,
+    },
+    /// An exact fraction grew past what a [`Rational`](crate::Rational) holds.
+    Overflow,
+    /// A variable that was asked for does not appear in the system.
+    UnknownVariable(String),
+}
+
+impl Display for SolverError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptySystem => f.write_str("The system does not contain any equations"),
+            Self::Inconsistent => f.write_str("The system is inconsistent and has no solution"),
+            Self::Underdetermined { rank, variables } => write!(
+                f,
+                "The system has infinitely many solutions: rank {rank} is less than the {variables} variables"
+            ),
+            Self::NotSquare {
+                equations,
+                variables,
+            } => write!(
+                f,
+                "The system has {equations} equations but {variables} variables, it must be square"
+            ),
+            Self::UnknownVariable(variable) => {
+                write!(f, "The variable {variable} does not appear in the system")
+            }
+            Self::Overflow => {
+                f.write_str("The fractions grew too large to solve the system exactly")
+            }
+        }
+    }
+}
+
+impl Error for SolverError {}
@@ -0,0 +1,90 @@
+use super::PIVOT_EPSILON;
+
+/// The LU factorization of a square system's coefficients, with partial
+/// pivoting, see [`Solver::factor`](crate::Solver::factor). It solves the same
+/// system for any constants without eliminating again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LuFactorization {
+    variables: Vec<String>,
+    /// `L` below the diagonal, with its unit diagonal left implicit, and `U` on
+    /// and above it.
+    lu: Vec<Vec<f64>>,
+    /// The equation that ended up in each row after pivoting.
+    permutation: Vec<usize>,
+}
+
+impl LuFactorization {
+    /// Factors the square `matrix`, returning `None` if it is singular.
+    pub(super) fn new(variables: Vec<String>, mut lu: Vec<Vec<f64>>) -> Option<Self> {
+        let size = lu.len();
+        let mut permutation: Vec<usize> = (0..size).collect();
+
+        for column in 0..size {
+            let pivot_row = (column..size)
+                .max_by(|a, b| lu[*a][column].abs().total_cmp(&lu[*b][column].abs()))
+                .unwrap();
+            if lu[pivot_row][column].abs() < PIVOT_EPSILON {
+                return None;
+            }
+
+            lu.swap(column, pivot_row);
+            permutation.swap(column, pivot_row);
+
+            let pivot_values = lu[column].clone();
+            for row in &mut lu[column + 1..] {
+                let factor = row[column] / pivot_values[column];
+                row[column] = factor;
+                if factor == 0.0 {
+                    continue;
+                }
+
+                for (value, pivot_value) in row.iter_mut().zip(&pivot_values).skip(column + 1) {
+                    *value -= factor * pivot_value;
+                }
+            }
+        }
+
+        Some(Self {
+            variables,
+            lu,
+            permutation,
+        })
+    }
+
+    /// The variables of the system, sorted by name, in the order
+    /// [`LuFactorization::solve_rhs`] returns their values.
+    pub fn variables(&self) -> &[String] {
+        &self.variables
+    }
+
+    /// Solves the system with `constants` in place of the constant of each
+    /// equation, in the order the equations were factored.
+    ///
+    /// # Panics
+    ///
+    /// If there is not exactly one constant per equation.
+    pub fn solve_rhs(&self, constants: &[f64]) -> Vec<f64> {
+        assert_eq!(
+            constants.len(),
+            self.lu.len(),
+            "expected one constant per equation"
+        );
+
+        // Forward substitution through `L`, then back substitution through `U`.
+        let mut values: Vec<f64> = self.permutation.iter().map(|row| constants[*row]).collect();
+        for row in 0..values.len() {
+            let known: f64 = (0..row)
+                .map(|column| self.lu[row][column] * values[column])
+                .sum();
+            values[row] -= known;
+        }
+        for row in (0..values.len()).rev() {
+            let known: f64 = (row + 1..values.len())
+                .map(|column| self.lu[row][column] * values[column])
+                .sum();
+            values[row] = (values[row] - known) / self.lu[row][row];
+        }
+
+        values
+    }
+}
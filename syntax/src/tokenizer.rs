@@ -1,34 +1,72 @@
 mod error;
+mod source;
 mod token;
 
-use std::io::BufRead;
+use alloc::string::String;
+use alloc::vec::Vec;
 
 pub use error::{TokenizerError, TokenizerResult};
-pub use token::{Token, TokenType};
+pub use source::{ByteSource, SliceSource};
+pub use token::{Token, TokenType, reconstruct};
 
-const DECIMAL_SEPARATOR: u8 = b'.';
+const DEFAULT_DECIMAL_SEPARATOR: u8 = b'.';
 
 #[derive(Debug, PartialEq, Eq)]
 enum NumberParseSection {
     Integer,
     Decimal,
+    Exponent,
 }
 
-pub struct Tokenizer<R: BufRead> {
+pub struct Tokenizer<R: ByteSource> {
     input: R,
+    line: usize,
     column: usize,
+    /// Bytes consumed so far across every line, not counting the lookahead.
+    position: usize,
     current_byte: Option<u8>,
+    decimal_separator: u8,
+    max_identifier_len: Option<usize>,
 }
 
-impl<R: BufRead> Tokenizer<R> {
+impl<R: ByteSource> Tokenizer<R> {
     pub fn new(input: R) -> Self {
         Self {
             input,
+            line: 1,
             column: 0,
+            position: 0,
             current_byte: None,
+            decimal_separator: DEFAULT_DECIMAL_SEPARATOR,
+            max_identifier_len: None,
         }
     }
 
+    /// Reads numbers with `separator` between their integer and decimal parts, such
+    /// as `b','` for `1,5`. Defaults to `b'.'`.
+    ///
+    /// With `b','`, a comma right after the digits of a number is always taken as
+    /// part of it, so `1,5` is `1.5` rather than `1`, [`TokenType::Comma`] and `5`.
+    /// Commas elsewhere still become [`TokenType::Comma`].
+    pub fn with_decimal_separator(mut self, separator: u8) -> Self {
+        self.decimal_separator = separator;
+        self
+    }
+
+    /// Rejects variable and function names longer than `len` bytes with
+    /// [`TokenizerError::IdentifierTooLong`] instead of reading them whole, which
+    /// bounds the memory a single name can take. Names have no limit by default.
+    pub fn with_max_identifier_len(mut self, len: usize) -> Self {
+        self.max_identifier_len = Some(len);
+        self
+    }
+
+    /// How many bytes of the input have been consumed so far, counting every line.
+    /// Unlike token columns, it never goes back, so it can drive a progress bar.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
     fn scan_token(&mut self) -> Option<TokenizerResult<Token>> {
         use TokenType::*;
         let current = self.consume_whitespace()?;
@@ -44,25 +82,56 @@ impl<R: BufRead> Tokenizer<R> {
         let token = match current {
             b'(' => push_token!(LeftParen),
             b')' => push_token!(RightParen),
+            b',' => push_token!(Comma),
+            b'|' => push_token!(Bar),
             b'+' => push_token!(Plus),
             b'-' => push_token!(Minus),
             b'*' => push_token!(Star),
             b'=' => push_token!(Equal),
             b'/' => push_token!(Slash),
             b'^' => push_token!(Hat),
+            b':' => {
+                lexeme.push(current);
+                self.consume_colon_equal(lexeme)
+            }
+            b'<' => {
+                lexeme.push(current);
+                self.consume_comparison(lexeme, Less, LessEqual)
+            }
+            b'>' => {
+                lexeme.push(current);
+                self.consume_comparison(lexeme, Greater, GreaterEqual)
+            }
             b'\\' => {
                 lexeme.push(current);
                 self.consume_function_name(lexeme)
             }
+            // The first byte of both `×` (U+00D7) and `÷` (U+00F7) in UTF-8.
+            0xC3 => {
+                lexeme.push(current);
+                self.consume_unicode_operator(lexeme)
+            }
             b'0'..=b'9' => {
                 lexeme.push(current);
                 self.consume_number(lexeme)
             }
+            // A decimal without its integer part, like `.5`. A `.` on its own is
+            // still unknown.
+            b'.' if self.decimal_separator == b'.'
+                && self.current_byte.is_some_and(|c| c.is_ascii_digit()) =>
+            {
+                lexeme.push(current);
+                self.consume_number(lexeme)
+            }
             b'A'..=b'Z' | b'a'..=b'z' | b'_' => {
                 lexeme.push(current);
                 self.consume_identifier(lexeme)
             }
-            a => Err(error::TokenizerError::UnknownCharacter(a, self.column)),
+            a => Err(error::TokenizerError::UnknownCharacter(
+                a,
+                self.line,
+                self.column.saturating_sub(1),
+            )),
         };
 
         Some(token)
@@ -80,25 +149,32 @@ impl<R: BufRead> Tokenizer<R> {
     ) -> TokenizerResult<Token> {
         let lexeme = self.lexeme_into_utf8(lexeme)?;
 
-        Ok(Token::new(token_type, lexeme, column - 1))
+        Ok(Token::new(token_type, lexeme, column.saturating_sub(1)).with_line(self.line))
     }
 
     fn advance(&mut self) -> Option<u8> {
-        let mut buf = [0u8; 1];
-        match self.input.read_exact(&mut buf) {
-            Ok(_) => {
+        let consumed = self.read_next();
+        if consumed.is_some() {
+            self.position += 1;
+        }
+        consumed
+    }
+
+    fn read_next(&mut self) -> Option<u8> {
+        match self.input.next_byte() {
+            Some(byte) => {
                 let current_byte = self.current_byte.take();
 
-                self.current_byte = Some(buf[0]);
+                self.current_byte = Some(byte);
                 // This will only happen on the last byte
                 self.column += 1;
                 current_byte
             }
             /*
-             * If we have finished reading from the Reader, it is still also possible that
+             * If we have finished reading from the source, it is still also possible that
              * we have one single byte remaining on the scanner, which would be the current byte
              */
-            Err(_) => {
+            None => {
                 self.column += 1;
                 self.current_byte.take()
             }
@@ -106,15 +182,62 @@ impl<R: BufRead> Tokenizer<R> {
     }
 
     fn consume_number(&mut self, mut lexeme: Vec<u8>) -> TokenizerResult<Token> {
-        // Parse the first digit.
-        let mut decimal: f64 = (lexeme[0] - 0x30) as f64;
-        let mut decimal_power = 0;
-        let mut current_part = NumberParseSection::Integer;
+        let malformed = TokenizerError::MalformedNumber(self.line, self.column.saturating_sub(1));
+        let mut current_part = match lexeme.first() {
+            Some(c) if c.is_ascii_digit() => NumberParseSection::Integer,
+            Some(c) if *c == self.decimal_separator => NumberParseSection::Decimal,
+            _ => return Err(malformed),
+        };
+
+        if lexeme == b"0" {
+            match self.current_byte {
+                Some(b'x') => return self.consume_radix_number(lexeme, 16),
+                Some(b'b') => return self.consume_radix_number(lexeme, 2),
+                _ => {}
+            }
+        }
+
         let first_col = self.column;
 
         while let Some(c) = self.current_byte {
-            if c == DECIMAL_SEPARATOR {
-                if current_part == NumberParseSection::Decimal {
+            if matches!(c, b'e' | b'E') && current_part != NumberParseSection::Exponent {
+                current_part = NumberParseSection::Exponent;
+                self.advance();
+                lexeme.push(c);
+
+                if let Some(sign @ (b'+' | b'-')) = self.current_byte {
+                    self.advance();
+                    lexeme.push(sign);
+                }
+                // Once the `e` is read it can no longer be a variable, so `2e` and
+                // `2e+` are errors rather than `2 * e`.
+                if !self.current_byte.is_some_and(|c| c.is_ascii_digit()) {
+                    return Err(malformed);
+                }
+                continue;
+            }
+
+            // Underscores group digits, as in `1_000`, so they must sit between two.
+            if c == b'_' {
+                if !lexeme.last().is_some_and(u8::is_ascii_digit) {
+                    return Err(malformed);
+                }
+                self.advance();
+                lexeme.push(c);
+
+                if !self.current_byte.is_some_and(|c| c.is_ascii_digit()) {
+                    return Err(malformed);
+                }
+                continue;
+            }
+
+            if c == self.decimal_separator {
+                if current_part != NumberParseSection::Integer {
+                    // `1.5.3` would otherwise read as `1.5` and `.3`. A `,` stays a
+                    // comma, so `1,5,3` still lists `1,5` and `3`.
+                    if c == b'.' {
+                        return Err(malformed);
+                    }
                     break;
                 }
                 current_part = NumberParseSection::Decimal;
@@ -127,23 +250,121 @@ impl<R: BufRead> Tokenizer<R> {
                 break;
             }
 
-            let current_value = (c - 0x30) as f64;
             lexeme.push(c);
+            self.advance();
+        }
 
-            match current_part {
-                NumberParseSection::Integer => {
-                    decimal *= 10f64;
-                    decimal += current_value;
+        // The lexeme only holds digits, one separator, underscores and an exponent at
+        // this point. Parsing it in one go avoids `powi`, which is not available
+        // without `std`. Literals too large for an `f64`, like `1e400`, would parse
+        // to infinity, which nothing downstream expects.
+        let digits: Vec<u8> = lexeme
+            .iter()
+            .filter(|c| **c != b'_')
+            .map(|c| {
+                if *c == self.decimal_separator {
+                    b'.'
+                } else {
+                    *c
                 }
-                NumberParseSection::Decimal => {
-                    decimal_power -= 1;
-                    decimal += current_value * 10f64.powi(decimal_power);
-                }
-            }
+            })
+            .collect();
+        let decimal = core::str::from_utf8(&digits)
+            .ok()
+            .and_then(|digits| digits.parse::<f64>().ok())
+            .filter(|decimal| decimal.is_finite())
+            .ok_or(malformed)?;
+
+        self.add_token_with_column(TokenType::Number(decimal), lexeme, first_col)
+    }
+
+    /// Reads the digits of an integer literal after its `0x` or `0b` prefix, which
+    /// is still the current byte.
+    fn consume_radix_number(&mut self, mut lexeme: Vec<u8>, radix: u32) -> TokenizerResult<Token> {
+        let first_col = self.column;
+        let malformed = TokenizerError::MalformedNumber(self.line, first_col.saturating_sub(1));
+
+        if let Some(prefix) = self.current_byte {
+            lexeme.push(prefix);
             self.advance();
         }
 
-        self.add_token_with_column(TokenType::Number(decimal), lexeme, first_col)
+        let mut value = 0f64;
+        let mut digits = 0;
+        while let Some(c) = self.current_byte {
+            let Some(digit) = (c as char).to_digit(radix) else {
+                break;
+            };
+            value = value * radix as f64 + digit as f64;
+            digits += 1;
+
+            lexeme.push(c);
+            self.advance();
+        }
+
+        // `0x` alone, or followed by something like `0b12`, is not a number, and
+        // neither is one too large for an `f64`.
+        if digits == 0
+            || !value.is_finite()
+            || self.current_byte.is_some_and(|c| c.is_ascii_alphanumeric())
+        {
+            return Err(malformed);
+        }
+
+        self.add_token_with_column(TokenType::Number(value), lexeme, first_col)
+    }
+
+    fn consume_comparison(
+        &mut self,
+        mut lexeme: Vec<u8>,
+        strict: TokenType,
+        inclusive: TokenType,
+    ) -> TokenizerResult<Token> {
+        let start = self.column;
+
+        if self.current_byte == Some(b'=') {
+            self.advance();
+            lexeme.push(b'=');
+            return self.add_token_with_column(inclusive, lexeme, start);
+        }
+
+        self.add_token_with_column(strict, lexeme, start)
+    }
+
+    /// A `:` is only valid as the start of `:=`.
+    fn consume_colon_equal(&mut self, mut lexeme: Vec<u8>) -> TokenizerResult<Token> {
+        let start = self.column;
+
+        if self.current_byte != Some(b'=') {
+            return Err(TokenizerError::UnknownCharacter(
+                b':',
+                self.line,
+                start.saturating_sub(1),
+            ));
+        }
+
+        self.advance();
+        lexeme.push(b'=');
+        self.add_token_with_column(TokenType::ColonEqual, lexeme, start)
+    }
+
+    fn consume_unicode_operator(&mut self, mut lexeme: Vec<u8>) -> TokenizerResult<Token> {
+        let start = self.column;
+
+        let token_type = match self.current_byte {
+            Some(0x97) => TokenType::Star,
+            Some(0xB7) => TokenType::Slash,
+            _ => {
+                return Err(TokenizerError::UnknownCharacter(
+                    lexeme[0],
+                    self.line,
+                    start.saturating_sub(1),
+                ));
+            }
+        };
+
+        lexeme.extend(self.advance());
+        self.add_token_with_column(token_type, lexeme, start)
     }
 
     fn consume_function_name(&mut self, mut lexeme: Vec<u8>) -> TokenizerResult<Token> {
@@ -154,6 +375,7 @@ impl<R: BufRead> Tokenizer<R> {
             if !c.is_ascii_alphanumeric() {
                 break;
             }
+            self.check_identifier_len(name.len() + 1, start)?;
             self.advance();
 
             name.push(c);
@@ -165,10 +387,42 @@ impl<R: BufRead> Tokenizer<R> {
         self.add_token_with_column(TokenType::FunctionName(name), lexeme, start)
     }
 
-    fn consume_identifier(&mut self, lexeme: Vec<u8>) -> TokenizerResult<Token> {
-        self.add_token(
+    fn check_identifier_len(&self, len: usize, start: usize) -> TokenizerResult<()> {
+        match self.max_identifier_len {
+            Some(max) if len > max => Err(TokenizerError::IdentifierTooLong(
+                self.line,
+                start.saturating_sub(1),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn consume_identifier(&mut self, mut lexeme: Vec<u8>) -> TokenizerResult<Token> {
+        let start = self.column;
+        self.check_identifier_len(lexeme.len(), start)?;
+
+        while let Some(c) = self.current_byte {
+            if !c.is_ascii_alphanumeric() && c != b'_' {
+                break;
+            }
+            self.check_identifier_len(lexeme.len() + 1, start)?;
+            self.advance();
+
+            lexeme.push(c);
+        }
+
+        // A lone `_` is far more likely a typo than a variable name.
+        if lexeme.iter().all(|c| *c == b'_') {
+            return Err(TokenizerError::UnderscoreIdentifier(
+                self.line,
+                start.saturating_sub(1),
+            ));
+        }
+
+        self.add_token_with_column(
             TokenType::Identifier(self.lexeme_into_utf8(lexeme.clone())?),
             lexeme,
+            start,
         )
     }
 
@@ -176,25 +430,53 @@ impl<R: BufRead> Tokenizer<R> {
         loop {
             let current = self.advance()?;
             match current {
-                b'\n' | b'\r' => {
-                    self.column = 0;
+                // The next byte returned by `advance` will be the first one of the
+                // line, which is reported as `column - 1`. A `\r` right before a
+                // `\n` leaves the count to it, so `\r\n` is a single line break.
+                b'\n' => {
+                    self.line += 1;
+                    self.column = 1;
+                }
+                b'\r' => {
+                    if self.current_byte != Some(b'\n') {
+                        self.line += 1;
+                    }
+                    self.column = 1;
                 }
                 b' ' | b'\t' => {}
+                b'#' => self.skip_comment(),
+                b'/' if self.current_byte == Some(b'/') => self.skip_comment(),
 
                 _ => break Some(current),
             }
         }
     }
 
+    /// Skips a `#` or `//` comment up to the line break that ends it, which is left
+    /// for `consume_whitespace` to reset the column.
+    fn skip_comment(&mut self) {
+        while !matches!(self.current_byte, None | Some(b'\n' | b'\r')) {
+            self.advance();
+        }
+    }
+
     fn lexeme_into_utf8(&self, lexeme: Vec<u8>) -> TokenizerResult<String> {
         match String::from_utf8(lexeme) {
             Ok(s) => Ok(s),
-            Err(_) => Err(error::TokenizerError::NoUtf8(self.column)),
+            Err(_) => Err(error::TokenizerError::NoUtf8(self.line, self.column)),
         }
     }
 }
 
-impl<R: BufRead> Iterator for Tokenizer<R> {
+impl<'a> Tokenizer<SliceSource<'a>> {
+    /// Reads the tokens of `input` straight from memory, with the same tokens and
+    /// columns as [`Tokenizer::new`] gives for a reader over the same bytes.
+    pub fn from_bytes(input: &'a [u8]) -> Self {
+        Self::new(SliceSource::new(input))
+    }
+}
+
+impl<R: ByteSource> Iterator for Tokenizer<R> {
     type Item = TokenizerResult<Token>;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -205,10 +487,10 @@ impl<R: BufRead> Iterator for Tokenizer<R> {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
-    use crate::tokenizer::Token;
     use crate::tokenizer::token::TokenType;
+    use crate::tokenizer::{Token, TokenizerError};
     use std::io::Cursor;
 
     #[test]
@@ -226,15 +508,10 @@ mod tests {
                     1
                 ),
                 Token::new(
-                    TokenType::Identifier(String::from("y")),
-                    String::from("y"),
+                    TokenType::Identifier(String::from("yz")),
+                    String::from("yz"),
                     3
                 ),
-                Token::new(
-                    TokenType::Identifier(String::from("z")),
-                    String::from("z"),
-                    4
-                ),
                 Token::new(
                     TokenType::Identifier(String::from("a")),
                     String::from("a"),
@@ -244,6 +521,31 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_multi_character_identifier() {
+        let source = "2alpha + beta_2 = x1y(gamma)";
+        let scanner = super::Tokenizer::new(Cursor::new(source));
+        let result: Vec<Token> = scanner.map(|t| t.unwrap()).collect();
+
+        let identifiers: Vec<(&str, usize)> = result
+            .iter()
+            .filter(|token| matches!(token.token_type, TokenType::Identifier(_)))
+            .map(|token| (token.lexeme.as_str(), token.column))
+            .collect();
+        assert_eq!(
+            identifiers,
+            [("alpha", 2), ("beta_2", 10), ("x1y", 19), ("gamma", 23)]
+        );
+        assert_eq!(result[0].token_type, TokenType::Number(2.0));
+
+        let scanner = super::Tokenizer::new(Cursor::new("x + __ = 1"));
+        let result: Result<Vec<Token>, _> = scanner.collect();
+        assert!(matches!(
+            result,
+            Err(TokenizerError::UnderscoreIdentifier(1, 5))
+        ));
+    }
+
     #[test]
     fn test_number_literal() {
         let source = "1.5 2.5 10 32.5 1.2";
@@ -262,6 +564,117 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_scientific_notation() {
+        let source = "6.022e23 1.5e3x 2E-4 7e+2";
+        let scanner = super::Tokenizer::new(Cursor::new(source));
+        let result: Vec<Token> = scanner.map(|t| t.unwrap()).collect();
+
+        assert_eq!(
+            result,
+            [
+                Token::new(TokenType::Number(6.022e23), String::from("6.022e23"), 1),
+                Token::new(TokenType::Number(1500.0), String::from("1.5e3"), 10),
+                Token::new(
+                    TokenType::Identifier(String::from("x")),
+                    String::from("x"),
+                    15
+                ),
+                Token::new(TokenType::Number(2e-4), String::from("2E-4"), 17),
+                Token::new(TokenType::Number(700.0), String::from("7e+2"), 22),
+            ]
+        );
+
+        // The exponent has no decimals, and the value has to fit in an `f64`.
+        for source in [
+            "2e = 1",
+            "x = 2e+",
+            "1.5E-x",
+            "3e1.5",
+            "x = 1e400",
+            "1e400x = 1",
+        ] {
+            let scanner = super::Tokenizer::new(Cursor::new(source));
+            let result: Result<Vec<Token>, _> = scanner.collect();
+
+            assert!(matches!(result, Err(TokenizerError::MalformedNumber(..))));
+        }
+    }
+
+    #[test]
+    fn test_leading_dot_decimals() {
+        let source = ".5x = .25 + 1";
+        let scanner = super::Tokenizer::new(Cursor::new(source));
+        let result: Vec<Token> = scanner.map(|t| t.unwrap()).collect();
+
+        assert_eq!(
+            result,
+            [
+                Token::new(TokenType::Number(0.5), String::from(".5"), 1),
+                Token::new(
+                    TokenType::Identifier(String::from("x")),
+                    String::from("x"),
+                    3
+                ),
+                Token::new(TokenType::Equal, String::from("="), 5),
+                Token::new(TokenType::Number(0.25), String::from(".25"), 7),
+                Token::new(TokenType::Plus, String::from("+"), 11),
+                Token::new(TokenType::Number(1.0), String::from("1"), 13),
+            ]
+        );
+
+        for (source, column) in [(". 5 = x", 1), ("x = .x", 5)] {
+            let scanner = super::Tokenizer::new(Cursor::new(source));
+            let result: Result<Vec<Token>, _> = scanner.collect();
+
+            assert!(
+                matches!(result, Err(TokenizerError::UnknownCharacter(b'.', 1, c)) if c == column)
+            );
+        }
+
+        let scanner = super::Tokenizer::new(Cursor::new("1..5"));
+        let result: Result<Vec<Token>, _> = scanner.collect();
+        assert!(matches!(result, Err(TokenizerError::MalformedNumber(1, 1))));
+
+        let scanner = super::Tokenizer::new(Cursor::new(".5e1 .5")).map(|t| t.unwrap().token_type);
+        assert_eq!(
+            scanner.collect::<Vec<_>>(),
+            [TokenType::Number(5.0), TokenType::Number(0.5)]
+        );
+    }
+
+    #[test]
+    fn test_digit_separators() {
+        let source = "1_000_000x = 2_500.000_1";
+        let scanner = super::Tokenizer::new(Cursor::new(source));
+        let result: Vec<Token> = scanner.map(|t| t.unwrap()).collect();
+
+        assert_eq!(
+            result,
+            [
+                Token::new(TokenType::Number(1e6), String::from("1_000_000"), 1),
+                Token::new(
+                    TokenType::Identifier(String::from("x")),
+                    String::from("x"),
+                    10
+                ),
+                Token::new(TokenType::Equal, String::from("="), 12),
+                Token::new(
+                    TokenType::Number(2500.0001),
+                    String::from("2_500.000_1"),
+                    14
+                ),
+            ]
+        );
+
+        for source in ["1__000 = x", "x = 1000_", "1_.5", "1._5", "2e_3", "1_x = 2"] {
+            let scanner = super::Tokenizer::new(Cursor::new(source));
+            let result: Result<Vec<Token>, _> = scanner.collect();
+
+            assert!(matches!(result, Err(TokenizerError::MalformedNumber(..))));
+        }
+    }
+
     #[test]
     fn test_sum_expression() {
         let source = "1.5x + 3y +2";
@@ -430,4 +843,386 @@ mod tests {
             ]
         )
     }
+
+    #[test]
+    fn test_comparison_operators() {
+        let source = "x < 1 <= y > 2 >= 3";
+
+        let scanner = super::Tokenizer::new(Cursor::new(source));
+        let result: Vec<Token> = scanner.map(|t| t.unwrap()).collect();
+
+        assert_eq!(
+            result,
+            [
+                Token::new(
+                    TokenType::Identifier(String::from("x")),
+                    String::from("x"),
+                    1
+                ),
+                Token::new(TokenType::Less, String::from("<"), 3),
+                Token::new(TokenType::Number(1.0), String::from("1"), 5),
+                Token::new(TokenType::LessEqual, String::from("<="), 7),
+                Token::new(
+                    TokenType::Identifier(String::from("y")),
+                    String::from("y"),
+                    10
+                ),
+                Token::new(TokenType::Greater, String::from(">"), 12),
+                Token::new(TokenType::Number(2.0), String::from("2"), 14),
+                Token::new(TokenType::GreaterEqual, String::from(">="), 16),
+                Token::new(TokenType::Number(3.0), String::from("3"), 19),
+            ]
+        )
+    }
+
+    #[test]
+    fn test_multi_digit_columns() {
+        let source = "10 + 200";
+        let scanner = super::Tokenizer::new(Cursor::new(source));
+        let result: Vec<Token> = scanner.map(|t| t.unwrap()).collect();
+
+        assert_eq!(
+            result,
+            [
+                Token::new(TokenType::Number(10.0), String::from("10"), 1),
+                Token::new(TokenType::Plus, String::from("+"), 4),
+                Token::new(TokenType::Number(200.0), String::from("200"), 6),
+            ]
+        );
+
+        let source = "1.5x";
+        let scanner = super::Tokenizer::new(Cursor::new(source));
+        let result: Vec<Token> = scanner.map(|t| t.unwrap()).collect();
+
+        assert_eq!(
+            result,
+            [
+                Token::new(TokenType::Number(1.5), String::from("1.5"), 1),
+                Token::new(
+                    TokenType::Identifier(String::from("x")),
+                    String::from("x"),
+                    4
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_columns_after_newline() {
+        let source = "x\n\n10 + y";
+        let scanner = super::Tokenizer::new(Cursor::new(source));
+        let result: Vec<Token> = scanner.map(|t| t.unwrap()).collect();
+
+        assert_eq!(
+            result,
+            [
+                Token::new(
+                    TokenType::Identifier(String::from("x")),
+                    String::from("x"),
+                    1
+                ),
+                Token::new(TokenType::Number(10.0), String::from("10"), 1).with_line(3),
+                Token::new(TokenType::Plus, String::from("+"), 4).with_line(3),
+                Token::new(
+                    TokenType::Identifier(String::from("y")),
+                    String::from("y"),
+                    6
+                )
+                .with_line(3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_radix_prefixes() {
+        let source = "0xFF + 0b101 = 0x1a";
+        let scanner = super::Tokenizer::new(Cursor::new(source));
+        let result: Vec<Token> = scanner.map(|t| t.unwrap()).collect();
+
+        assert_eq!(
+            result,
+            [
+                Token::new(TokenType::Number(255.0), String::from("0xFF"), 1),
+                Token::new(TokenType::Plus, String::from("+"), 6),
+                Token::new(TokenType::Number(5.0), String::from("0b101"), 8),
+                Token::new(TokenType::Equal, String::from("="), 14),
+                Token::new(TokenType::Number(26.0), String::from("0x1a"), 16),
+            ]
+        );
+
+        let huge = format!("0x{}", "F".repeat(300));
+        for source in ["0x", "0bz", "2 + 0b102", &huge] {
+            let scanner = super::Tokenizer::new(Cursor::new(source));
+            let result: Result<Vec<Token>, _> = scanner.collect();
+
+            assert!(matches!(result, Err(TokenizerError::MalformedNumber(..))));
+        }
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let source = "2x + 3,5y # note\r\n-\\sqrt(4) := |z| × 0x1f\n\t<= 1.5 ÷ w";
+        let from_reader: Vec<Token> = super::Tokenizer::new(Cursor::new(source))
+            .map(|t| t.unwrap())
+            .collect();
+        let from_bytes: Vec<Token> = super::Tokenizer::from_bytes(source.as_bytes())
+            .map(|t| t.unwrap())
+            .collect();
+        assert_eq!(from_bytes, from_reader);
+        assert_eq!(from_bytes.len(), 22);
+
+        let error = super::Tokenizer::from_bytes(b"x +\n  1 @ 2").find_map(|t| t.err());
+        assert!(matches!(
+            error,
+            Some(TokenizerError::UnknownCharacter(b'@', 2, 5))
+        ));
+    }
+
+    #[test]
+    fn test_comments() {
+        let tokenize = |source: &str| -> Vec<Token> {
+            let scanner = super::Tokenizer::new(Cursor::new(source));
+            scanner.map(|t| t.unwrap()).collect()
+        };
+
+        assert_eq!(tokenize("x = 1 # note"), tokenize("x = 1"));
+        assert_eq!(tokenize("x = 1 // note"), tokenize("x = 1"));
+        assert_eq!(tokenize("x // a / b\r\n= 1#"), tokenize("x\n= 1"));
+        assert_eq!(tokenize("x = 4/2").len(), 5);
+    }
+
+    #[test]
+    fn test_max_identifier_len() {
+        let source = format!("2 + \\{} = 1", "a".repeat(1000));
+        let scanner =
+            super::Tokenizer::new(Cursor::new(source.as_str())).with_max_identifier_len(64);
+        let result: Result<Vec<Token>, _> = scanner.collect();
+        assert!(matches!(
+            result,
+            Err(TokenizerError::IdentifierTooLong(1, 5))
+        ));
+
+        let scanner =
+            super::Tokenizer::new(Cursor::new("\\sqrt(x) = 1")).with_max_identifier_len(4);
+        assert!(scanner.collect::<Result<Vec<Token>, _>>().is_ok());
+
+        let scanner = super::Tokenizer::new(Cursor::new(source.as_str()));
+        assert!(scanner.collect::<Result<Vec<Token>, _>>().is_ok());
+    }
+
+    #[test]
+    fn test_underscore_identifier() {
+        let scanner = super::Tokenizer::new(Cursor::new("2x + _ = 1"));
+        let result: Result<Vec<Token>, _> = scanner.collect();
+
+        assert!(matches!(
+            result,
+            Err(TokenizerError::UnderscoreIdentifier(1, 6))
+        ));
+    }
+
+    #[test]
+    fn test_decimal_separator() {
+        let scanner = super::Tokenizer::new(Cursor::new("1,5x = 3")).with_decimal_separator(b',');
+        let result: Vec<Token> = scanner.map(|t| t.unwrap()).collect();
+
+        assert_eq!(
+            result,
+            [
+                Token::new(TokenType::Number(1.5), String::from("1,5"), 1),
+                Token::new(
+                    TokenType::Identifier(String::from("x")),
+                    String::from("x"),
+                    4
+                ),
+                Token::new(TokenType::Equal, String::from("="), 6),
+                Token::new(TokenType::Number(3.0), String::from("3"), 8),
+            ]
+        );
+
+        let scanner = super::Tokenizer::new(Cursor::new("1,5"));
+        let result: Vec<TokenType> = scanner.map(|t| t.unwrap().token_type).collect();
+        assert_eq!(
+            result,
+            [
+                TokenType::Number(1.0),
+                TokenType::Comma,
+                TokenType::Number(5.0)
+            ]
+        );
+
+        let scanner = super::Tokenizer::new(Cursor::new("1.5")).with_decimal_separator(b',');
+        let result: Result<Vec<Token>, _> = scanner.collect();
+        assert!(matches!(
+            result,
+            Err(TokenizerError::UnknownCharacter(b'.', 1, 2))
+        ));
+    }
+
+    #[test]
+    fn test_commas() {
+        let source = "x, y";
+        let scanner = super::Tokenizer::new(Cursor::new(source));
+        let result: Vec<Token> = scanner.map(|t| t.unwrap()).collect();
+
+        assert_eq!(
+            result,
+            [
+                Token::new(
+                    TokenType::Identifier(String::from("x")),
+                    String::from("x"),
+                    1
+                ),
+                Token::new(TokenType::Comma, String::from(","), 2),
+                Token::new(
+                    TokenType::Identifier(String::from("y")),
+                    String::from("y"),
+                    4
+                ),
+            ]
+        );
+
+        let source = "\\f(a, b)";
+        let scanner = super::Tokenizer::new(Cursor::new(source));
+        let result: Vec<TokenType> = scanner.map(|t| t.unwrap().token_type).collect();
+
+        assert_eq!(
+            result,
+            [
+                TokenType::FunctionName(String::from("f")),
+                TokenType::LeftParen,
+                TokenType::Identifier(String::from("a")),
+                TokenType::Comma,
+                TokenType::Identifier(String::from("b")),
+                TokenType::RightParen,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_columns_after_crlf() {
+        let source = "1\r\n+ 2\r3";
+        let scanner = super::Tokenizer::new(Cursor::new(source));
+        let result: Vec<Token> = scanner.map(|t| t.unwrap()).collect();
+
+        assert_eq!(
+            result,
+            [
+                Token::new(TokenType::Number(1.0), String::from("1"), 1),
+                Token::new(TokenType::Plus, String::from("+"), 1).with_line(2),
+                Token::new(TokenType::Number(2.0), String::from("2"), 3).with_line(2),
+                Token::new(TokenType::Number(3.0), String::from("3"), 1).with_line(3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_byte_slice_source() {
+        let source: &[u8] = b"2x = 0.125";
+        let scanner = super::Tokenizer::new(source);
+        let result: Vec<TokenType> = scanner.map(|t| t.unwrap().token_type).collect();
+
+        assert_eq!(
+            result,
+            [
+                TokenType::Number(2.0),
+                TokenType::Identifier(String::from("x")),
+                TokenType::Equal,
+                TokenType::Number(0.125),
+            ]
+        );
+    }
+
+    #[cfg(feature = "fuzz")]
+    #[test]
+    fn fuzz_random_bytes() {
+        // xorshift64*, so the fuzzer is deterministic and needs no dependencies.
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        let mut next_byte = || {
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            (state.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 56) as u8
+        };
+
+        const ALPHABET: &[u8] = b"0123456789.eExyz_\\()+-*/^=<> \t\r\n";
+
+        for iteration in 0..20_000 {
+            let length = next_byte() as usize % 32;
+            let input: Vec<u8> = (0..length)
+                .map(|_| {
+                    let byte = next_byte();
+                    // Half of the inputs only use bytes the tokenizer knows about.
+                    if iteration % 2 == 0 {
+                        ALPHABET[byte as usize % ALPHABET.len()]
+                    } else {
+                        byte
+                    }
+                })
+                .collect();
+
+            let tokenizer = super::Tokenizer::new(Cursor::new(input));
+            for token in tokenizer {
+                let _ = token;
+            }
+        }
+    }
+
+    #[test]
+    fn test_unicode_operators() {
+        let token_types = |source: &str| -> Vec<TokenType> {
+            super::Tokenizer::new(Cursor::new(source))
+                .map(|t| t.unwrap().token_type)
+                .collect()
+        };
+
+        assert_eq!(token_types("2×x ÷ 4 = 1"), token_types("2*x / 4 = 1"));
+
+        let tokens: Vec<Token> = super::Tokenizer::new(Cursor::new("2×x"))
+            .map(|t| t.unwrap())
+            .collect();
+        assert_eq!(tokens[1], Token::new(TokenType::Star, String::from("×"), 2));
+        assert_eq!(tokens[2].column, 4);
+
+        let result: Vec<_> = super::Tokenizer::new(Cursor::new("2Ã")).collect();
+        assert!(matches!(
+            result[1],
+            Err(TokenizerError::UnknownCharacter(0xC3, 1, 2))
+        ));
+    }
+
+    #[test]
+    fn test_colon_equal() {
+        let tokens: Vec<Token> = super::Tokenizer::new(Cursor::new("y := 1"))
+            .map(|t| t.unwrap())
+            .collect();
+        assert_eq!(
+            tokens[1],
+            Token::new(TokenType::ColonEqual, String::from(":="), 3)
+        );
+        assert_eq!(tokens[2].column, 6);
+
+        let result: Vec<_> = super::Tokenizer::new(Cursor::new("y : 1")).collect();
+        assert!(matches!(
+            result[1],
+            Err(TokenizerError::UnknownCharacter(b':', 1, 3))
+        ));
+    }
+
+    #[test]
+    fn test_position() {
+        let source = "2x + 10\n= y # done";
+        let mut tokenizer = super::Tokenizer::new(Cursor::new(source));
+        assert_eq!(tokenizer.position(), 0);
+
+        let mut positions = Vec::new();
+        while let Some(token) = tokenizer.next() {
+            token.unwrap();
+            positions.push(tokenizer.position());
+        }
+
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(positions[..3], [1, 2, 4]);
+        assert_eq!(tokenizer.position(), source.len());
+    }
 }
@@ -1,21 +1,113 @@
-use std::error::Error;
-use std::fmt::{Display, Formatter};
+use core::error::Error;
+use core::fmt::{Display, Formatter};
 
 pub type TokenizerResult<T> = Result<T, TokenizerError>;
 
+/// Every variant ends with the line and the column where the error was found,
+/// in that order, both counted from 1.
 #[derive(Debug)]
 pub enum TokenizerError {
-    UnknownCharacter(u8, usize),
-    NoUtf8(usize),
+    UnknownCharacter(u8, usize, usize),
+    NoUtf8(usize, usize),
+    MalformedNumber(usize, usize),
+    UnderscoreIdentifier(usize, usize),
+    IdentifierTooLong(usize, usize),
+}
+
+impl TokenizerError {
+    /// A stable identifier for the kind of error, meant for branching on instead
+    /// of the message.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UnknownCharacter(..) => "unknown_character",
+            Self::NoUtf8(..) => "no_utf8",
+            Self::MalformedNumber(..) => "malformed_number",
+            Self::UnderscoreIdentifier(..) => "underscore_identifier",
+            Self::IdentifierTooLong(..) => "identifier_too_long",
+        }
+    }
+
+    /// The line where the error was found.
+    pub fn line(&self) -> usize {
+        self.location().0
+    }
+
+    /// The column where the error was found.
+    pub fn column(&self) -> usize {
+        self.location().1
+    }
+
+    fn location(&self) -> (usize, usize) {
+        match self {
+            Self::UnknownCharacter(_, line, column)
+            | Self::NoUtf8(line, column)
+            | Self::MalformedNumber(line, column)
+            | Self::UnderscoreIdentifier(line, column)
+            | Self::IdentifierTooLong(line, column) => (*line, *column),
+        }
+    }
 }
 
 impl Display for TokenizerError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
-            Self::UnknownCharacter(c, col) => write!(f, "Character {c} is not recognized by the tokenizer in column {col}"),
-            Self::NoUtf8(col) => write!(f, "Input string contains non-UTF8 sequences in column {col}"),
+            Self::UnknownCharacter(c, line, col) => write!(
+                f,
+                "Character {c} is not recognized by the tokenizer in line {line}, column {col}"
+            ),
+            Self::NoUtf8(line, col) => write!(
+                f,
+                "Input string contains non-UTF8 sequences in line {line}, column {col}"
+            ),
+            Self::MalformedNumber(line, col) => {
+                write!(f, "Malformed number literal in line {line}, column {col}")
+            }
+            Self::UnderscoreIdentifier(line, col) => write!(
+                f,
+                "Variables cannot be made only of underscores, found one in line {line}, column {col}"
+            ),
+            Self::IdentifierTooLong(line, col) => {
+                write!(f, "Name starting in line {line}, column {col} is too long")
+            }
         }
     }
 }
 
-impl Error for TokenizerError {}
\ No newline at end of file
+impl Error for TokenizerError {}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::tokenizer::TokenizerError;
+
+    #[test]
+    fn test_codes() {
+        assert_eq!(
+            TokenizerError::UnknownCharacter(b'@', 1, 1).code(),
+            "unknown_character"
+        );
+        assert_eq!(TokenizerError::NoUtf8(1, 1).code(), "no_utf8");
+        assert_eq!(
+            TokenizerError::MalformedNumber(1, 1).code(),
+            "malformed_number"
+        );
+        assert_eq!(
+            TokenizerError::UnderscoreIdentifier(1, 1).code(),
+            "underscore_identifier"
+        );
+        assert_eq!(
+            TokenizerError::IdentifierTooLong(1, 1).code(),
+            "identifier_too_long"
+        );
+    }
+
+    #[test]
+    fn test_line_and_column() {
+        let error = TokenizerError::MalformedNumber(3, 7);
+
+        assert_eq!((error.line(), error.column()), (3, 7));
+        assert_eq!(
+            error.to_string(),
+            "Malformed number literal in line 3, column 7"
+        );
+    }
+}
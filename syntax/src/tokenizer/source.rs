@@ -0,0 +1,48 @@
+/// A source of bytes the [`Tokenizer`](super::Tokenizer) reads one at a time.
+///
+/// With the `std` feature every [`BufRead`](std::io::BufRead) is a source, which
+/// already includes `&[u8]`. Without it, byte slices are the only input.
+pub trait ByteSource {
+    /// Returns the next byte, or `None` once the input is exhausted.
+    fn next_byte(&mut self) -> Option<u8>;
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::BufRead> ByteSource for R {
+    fn next_byte(&mut self) -> Option<u8> {
+        let mut buf = [0u8; 1];
+        self.read_exact(&mut buf).ok().map(|_| buf[0])
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl ByteSource for &[u8] {
+    fn next_byte(&mut self) -> Option<u8> {
+        let (first, rest) = self.split_first()?;
+        *self = rest;
+        Some(*first)
+    }
+}
+
+/// An in-memory [`ByteSource`] over a byte slice, built by
+/// [`Tokenizer::from_bytes`](super::Tokenizer::from_bytes). Unlike going through
+/// [`BufRead`](std::io::BufRead), reading a byte is just an index.
+#[derive(Debug, Clone)]
+pub struct SliceSource<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> SliceSource<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+}
+
+impl ByteSource for SliceSource<'_> {
+    fn next_byte(&mut self) -> Option<u8> {
+        let byte = self.bytes.get(self.position).copied()?;
+        self.position += 1;
+        Some(byte)
+    }
+}
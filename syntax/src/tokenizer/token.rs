@@ -1,3 +1,5 @@
+use alloc::string::String;
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum TokenType {
     Number(f64),
@@ -9,23 +11,176 @@ pub enum TokenType {
     Minus,
     LeftParen,
     RightParen,
+    Comma,
+    Bar,
     Equal,
+    /// `:=`, written for definitions such as `y := 2x + 1`.
+    ColonEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
     Hat,
 }
 
+impl TokenType {
+    pub fn is_number(&self) -> bool {
+        matches!(self, Self::Number(_))
+    }
+
+    pub fn is_identifier(&self) -> bool {
+        matches!(self, Self::Identifier(_))
+    }
+
+    /// Whether this is one of the arithmetic operators `+`, `-`, `*`, `/` or `^`.
+    pub fn is_operator(&self) -> bool {
+        matches!(
+            self,
+            Self::Plus | Self::Minus | Self::Star | Self::Slash | Self::Hat
+        )
+    }
+
+    /// Whether this is one of `=`, `:=`, `<`, `<=`, `>` or `>=`.
+    pub fn is_relation(&self) -> bool {
+        matches!(
+            self,
+            Self::Equal
+                | Self::ColonEqual
+                | Self::Less
+                | Self::LessEqual
+                | Self::Greater
+                | Self::GreaterEqual
+        )
+    }
+
+    /// Binding power of binary operators and relations, higher binds tighter.
+    /// Every other token returns `None`.
+    pub fn precedence(&self) -> Option<u8> {
+        match self {
+            _ if self.is_relation() => Some(1),
+            Self::Plus | Self::Minus => Some(2),
+            Self::Star | Self::Slash => Some(3),
+            Self::Hat => Some(4),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Token {
     pub token_type: TokenType,
     pub lexeme: String,
+    /// The 1-based line the token is on. `\n`, `\r\n` and a lone `\r` each end a
+    /// line.
+    pub line: usize,
     pub column: usize,
 }
 
 impl Token {
+    /// A token on the first line, see [`Token::with_line`].
     pub fn new(token_type: TokenType, lexeme: String, column: usize) -> Self {
         Self {
             token_type,
             lexeme,
+            line: 1,
             column,
         }
     }
+
+    pub fn with_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    pub fn is_number(&self) -> bool {
+        self.token_type.is_number()
+    }
+
+    pub fn is_identifier(&self) -> bool {
+        self.token_type.is_identifier()
+    }
+
+    pub fn is_operator(&self) -> bool {
+        self.token_type.is_operator()
+    }
+
+    pub fn is_relation(&self) -> bool {
+        self.token_type.is_relation()
+    }
+
+    /// Length of the lexeme in bytes.
+    pub fn len(&self) -> usize {
+        self.lexeme.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lexeme.is_empty()
+    }
+}
+
+/// Writes the tokens back as source text, one space between tokens except right
+/// after an opening parenthesis or function name and before a closing parenthesis
+/// or comma. `2x+3` becomes `2 x + 3`.
+pub fn reconstruct(tokens: &[Token]) -> String {
+    let mut source = String::new();
+    let mut previous: Option<&TokenType> = None;
+
+    for token in tokens {
+        let glued = matches!(
+            previous,
+            None | Some(TokenType::LeftParen | TokenType::FunctionName(_))
+        ) || matches!(token.token_type, TokenType::RightParen | TokenType::Comma);
+
+        if !glued {
+            source.push(' ');
+        }
+        source.push_str(&token.lexeme);
+        previous = Some(&token.token_type);
+    }
+
+    source
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::tokenizer::{Token, TokenType, Tokenizer, reconstruct};
+
+    #[test]
+    fn test_precedence_ordering() {
+        let plus = TokenType::Plus.precedence().unwrap();
+        let star = TokenType::Star.precedence().unwrap();
+        let hat = TokenType::Hat.precedence().unwrap();
+
+        assert!(plus < star && star < hat);
+        assert_eq!(TokenType::Minus.precedence(), Some(plus));
+        assert_eq!(TokenType::Slash.precedence(), Some(star));
+        assert!(TokenType::Equal.precedence().unwrap() < plus);
+        assert_eq!(TokenType::LeftParen.precedence(), None);
+    }
+
+    #[test]
+    fn test_predicates() {
+        let number = Token::new(TokenType::Number(2.0), String::from("2"), 1);
+
+        assert!(number.is_number());
+        assert!(!number.is_operator());
+        assert!(TokenType::Identifier(String::from("x")).is_identifier());
+        assert!(TokenType::Hat.is_operator());
+        assert!(TokenType::LessEqual.is_relation());
+        assert!(!TokenType::Equal.is_operator());
+    }
+
+    #[test]
+    fn test_reconstruct() {
+        let tokens: Vec<Token> = Tokenizer::new("2x+3".as_bytes())
+            .map(|t| t.unwrap())
+            .collect();
+        assert_eq!(reconstruct(&tokens), "2 x + 3");
+        assert_eq!(tokens[1].len(), 1);
+
+        let tokens: Vec<Token> = Tokenizer::new("\\sqrt( 4 )*x>=10.5".as_bytes())
+            .map(|t| t.unwrap())
+            .collect();
+        assert_eq!(reconstruct(&tokens), "\\sqrt(4) * x >= 10.5");
+    }
 }
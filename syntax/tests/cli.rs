@@ -0,0 +1,72 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn sel(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_sel"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(args: &[&str]) -> String {
+    let output = sel(args, "");
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_formats() {
+    let equation = "2x_1 + 4 = y - 1";
+
+    assert_eq!(
+        stdout(&["--format", "sexpr", equation]),
+        "(= (+ (* 2 x_1) (* -1 y)) -5)\n"
+    );
+    assert_eq!(stdout(&["--format", "infix", equation]), "2x_1 - y = -5\n");
+    assert_eq!(stdout(&["--format=latex", equation]), "2x_{1} - y = -5\n");
+    assert_eq!(
+        stdout(&["--format", "json", equation]),
+        "{\"terms\":{\"x_1\":2,\"y\":-1},\"constant\":-5}\n"
+    );
+    assert_eq!(
+        stdout(&[equation]),
+        stdout(&["--format", "infix", equation])
+    );
+}
+
+#[test]
+fn test_stdin() {
+    let output = sel(&["--format", "json"], "x = 1\n\n3 = 3\n");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "{\"terms\":{\"x\":1},\"constant\":1}\n{\"terms\":{},\"constant\":0}\n"
+    );
+}
+
+#[test]
+fn test_errors() {
+    let output = sel(&["x * y = 1", "x = 2"], "");
+    assert!(!output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "x = 2\n");
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .starts_with("x * y = 1: ")
+    );
+
+    let output = sel(&["--format", "yaml", "x = 1"], "");
+    assert_eq!(output.status.code(), Some(2));
+}